@@ -2,9 +2,10 @@ use raytracer::camera::Camera;
 use raytracer::colors::Color;
 use raytracer::light::PointLight;
 use raytracer::material::Material;
+use raytracer::plane::plane;
 use raytracer::render::render;
 use raytracer::sphere::sphere;
-use raytracer::transformations::{scale, translate, view_transform};
+use raytracer::transformations::{rotate_x, scale, translate, view_transform};
 use raytracer::tuple::Tuple;
 use raytracer::world::World;
 use std::f64::consts::PI;
@@ -13,20 +14,17 @@ use std::sync::Arc;
 fn generate_world() -> World {
     let mut world = World::new();
 
-    let floor = sphere();
-    let floor_transform = scale(10.0, 0.01, 10.0);
+    let floor = plane();
     let mut floor_material = Material::default();
     floor_material.color = Color::new(1.0, 0.9, 0.9);
     floor_material.specular = 0.0;
     let mut guard = floor.lock().unwrap();
-    guard.set_transformation(&floor_transform);
     guard.set_material(&floor_material);
 
     world.objects.push(floor.clone());
 
-    let left_wall = sphere();
-    let left_wall_transform = scale(10.0, 0.01, 10.0)
-        .rotate_x(PI / 2.0)
+    let left_wall = plane();
+    let left_wall_transform = rotate_x(PI / 2.0)
         .rotate_y(-PI / 4.0)
         .translate(0.0, 0.0, 5.0);
     let mut guard = left_wall.lock().unwrap();
@@ -34,9 +32,8 @@ fn generate_world() -> World {
     guard.set_transformation(&left_wall_transform);
     world.objects.push(left_wall.clone());
 
-    let right_wall = sphere();
-    let right_wall_transform = scale(10.0, 0.01, 10.0)
-        .rotate_x(PI / 2.0)
+    let right_wall = plane();
+    let right_wall_transform = rotate_x(PI / 2.0)
         .rotate_y(PI / 4.0)
         .translate(0.0, 0.0, 5.0);
     let mut guard = right_wall.lock().unwrap();
@@ -78,13 +75,14 @@ fn generate_world() -> World {
     world.objects.push(left_sphere.clone());
 
     let light = PointLight::new(Tuple::point(-10.0, 10.0, -10.0), Color::new(1.0, 1.0, 1.0));
-    world.light_sources.push(light);
+    world.light_sources.push(Box::new(light));
 
     world
 }
 
 pub fn main() {
-    let w = generate_world();
+    let mut w = generate_world();
+    w.build_bvh();
     let mut c = Camera::new(1000, 500, PI / 3.0);
     let from = Tuple::point(0.0, 1.5, -5.0);
     let to = Tuple::point(0.0, 1.0, 0.0);
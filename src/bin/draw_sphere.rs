@@ -54,7 +54,15 @@ pub fn main() {
 
                     let material = hit.scene_object.lock().unwrap().material();
 
-                    let color = lighting(&material, &light, point, eye, normal, false);
+                    let color = lighting(
+                        &material,
+                        &light,
+                        point,
+                        point,
+                        eye,
+                        normal,
+                        Color::new(1.0, 1.0, 1.0),
+                    );
 
                     canvas.write_pixel(x, y, color);
                 }
@@ -0,0 +1,209 @@
+use crate::bounding_box::BoundingBox;
+use crate::intersection::{intersect_with_stats, Intersections};
+use crate::ray::Ray;
+use crate::scene_object::MutSceneObject;
+use std::sync::Arc;
+
+// Nodes with this many objects or fewer are never split
+const MAX_LEAF_SIZE: usize = 4;
+
+// Rough cost of testing a ray against a box relative to testing it against an object
+const TRAVERSAL_COST: f64 = 0.125;
+
+#[derive(Debug, Clone)]
+enum BvhNode {
+    Leaf {
+        bounds: BoundingBox,
+        objects: Vec<MutSceneObject>,
+    },
+    Interior {
+        bounds: BoundingBox,
+        left: Box<BvhNode>,
+        right: Box<BvhNode>,
+    },
+}
+
+// Counters for how much work a single query did
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct BvhStats {
+    pub nodes_visited: usize,
+    pub objects_tested: usize,
+}
+
+// Bounding volume hierarchy over a set of objects, split using the surface area heuristic.
+// Objects without finite bounds (like planes) can't be placed in the tree and are tested
+// against every ray instead.
+#[derive(Debug, Clone)]
+pub struct Bvh {
+    root: Option<BvhNode>,
+    unbounded: Vec<MutSceneObject>,
+    // Everything the tree was built over, in order, to tell when the objects have changed
+    objects: Vec<MutSceneObject>,
+}
+
+impl Bvh {
+    pub fn new(objects: Vec<MutSceneObject>) -> Bvh {
+        let mut bounded = Vec::new();
+        let mut unbounded = Vec::new();
+        for object in objects.iter().cloned() {
+            let bounds = object.lock().unwrap().parent_space_bounds();
+            if bounds.is_finite() {
+                bounded.push((bounds, object));
+            } else {
+                unbounded.push(object);
+            }
+        }
+
+        let root = if bounded.is_empty() {
+            None
+        } else {
+            Some(build(bounded))
+        };
+        Bvh {
+            root,
+            unbounded,
+            objects,
+        }
+    }
+
+    // Whether the tree was built over exactly these objects. Only compares pointers, which is
+    // cheap next to intersecting the objects, and catches objects added, removed or replaced
+    // since it was built.
+    pub fn is_built_over(&self, objects: &[MutSceneObject]) -> bool {
+        self.objects.len() == objects.len()
+            && self
+                .objects
+                .iter()
+                .zip(objects.iter())
+                .all(|(a, b)| Arc::ptr_eq(a, b))
+    }
+
+    pub fn bounds(&self) -> BoundingBox {
+        if !self.unbounded.is_empty() {
+            return BoundingBox::infinite();
+        }
+        match &self.root {
+            None => BoundingBox::empty(),
+            Some(node) => *node.bounds(),
+        }
+    }
+
+    pub fn intersect(&self, ray: Ray) -> Intersections {
+        self.intersect_with_stats(ray, &mut BvhStats::default())
+    }
+
+    // Same as `intersect`, also adding up the work done for this ray into `stats`, including in
+    // the BVHs of groups inside the tree
+    pub fn intersect_with_stats(&self, ray: Ray, stats: &mut BvhStats) -> Intersections {
+        let mut res = Intersections::new();
+        for object in self.unbounded.iter() {
+            stats.objects_tested += 1;
+            res.concat(intersect_with_stats(object.clone(), ray, stats));
+        }
+        if let Some(root) = &self.root {
+            root.intersect(ray, stats, &mut res);
+        }
+        res
+    }
+}
+
+impl BvhNode {
+    fn bounds(&self) -> &BoundingBox {
+        match self {
+            BvhNode::Leaf { bounds, .. } => bounds,
+            BvhNode::Interior { bounds, .. } => bounds,
+        }
+    }
+
+    fn intersect(&self, ray: Ray, stats: &mut BvhStats, res: &mut Intersections) {
+        stats.nodes_visited += 1;
+        if !self.bounds().intersects(ray) {
+            return;
+        }
+
+        match self {
+            BvhNode::Leaf { objects, .. } => {
+                for object in objects.iter() {
+                    stats.objects_tested += 1;
+                    res.concat(intersect_with_stats(object.clone(), ray, stats));
+                }
+            }
+            BvhNode::Interior { left, right, .. } => {
+                left.intersect(ray, stats, res);
+                right.intersect(ray, stats, res);
+            }
+        }
+    }
+}
+
+fn centroid_axis(bounds: &BoundingBox, axis: usize) -> f64 {
+    let c = bounds.centroid();
+    match axis {
+        0 => c.x,
+        1 => c.y,
+        _ => c.z,
+    }
+}
+
+fn build(mut objects: Vec<(BoundingBox, MutSceneObject)>) -> BvhNode {
+    let bounds = objects
+        .iter()
+        .fold(BoundingBox::empty(), |acc, (b, _)| acc.merge(b));
+
+    if objects.len() <= MAX_LEAF_SIZE {
+        return leaf(bounds, objects);
+    }
+
+    // Try every split position along every axis, sorting by centroid, and keep the one with
+    // the lowest expected cost: each side's object count weighted by how likely a ray that
+    // hits the parent box is to also hit that side's box
+    let mut best: Option<(usize, usize, f64)> = None;
+    for axis in 0..3 {
+        objects.sort_by(|(a, _), (b, _)| centroid_axis(a, axis).total_cmp(&centroid_axis(b, axis)));
+
+        let n = objects.len();
+        let mut right_areas = vec![0.0; n];
+        let mut acc = BoundingBox::empty();
+        for i in (1..n).rev() {
+            acc = acc.merge(&objects[i].0);
+            right_areas[i] = acc.surface_area();
+        }
+
+        let mut acc = BoundingBox::empty();
+        for split in 1..n {
+            acc = acc.merge(&objects[split - 1].0);
+            let cost = acc.surface_area() * split as f64 + right_areas[split] * (n - split) as f64;
+            if best.is_none_or(|(_, _, best_cost)| cost < best_cost) {
+                best = Some((axis, split, cost));
+            }
+        }
+    }
+
+    let (axis, split, cost) = best.unwrap();
+    let parent_area = bounds.surface_area();
+    let leaf_cost = objects.len() as f64;
+    let split_cost = if parent_area > 0.0 {
+        TRAVERSAL_COST + cost / parent_area
+    } else {
+        leaf_cost
+    };
+
+    if split_cost >= leaf_cost {
+        return leaf(bounds, objects);
+    }
+
+    objects.sort_by(|(a, _), (b, _)| centroid_axis(a, axis).total_cmp(&centroid_axis(b, axis)));
+    let right = objects.split_off(split);
+    BvhNode::Interior {
+        bounds,
+        left: Box::new(build(objects)),
+        right: Box::new(build(right)),
+    }
+}
+
+fn leaf(bounds: BoundingBox, objects: Vec<(BoundingBox, MutSceneObject)>) -> BvhNode {
+    BvhNode::Leaf {
+        bounds,
+        objects: objects.into_iter().map(|(_, o)| o).collect(),
+    }
+}
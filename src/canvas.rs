@@ -105,6 +105,33 @@ impl Canvas {
         stream_writer.finish().unwrap();
     }
 
+    // Reads an 8 or 16 bit PNG of any color type. Alpha is dropped and gray turns into equal
+    // red, green and blue.
+    pub fn from_png_file(path: &str) -> Result<Canvas, png::DecodingError> {
+        let mut decoder = png::Decoder::new(File::open(path)?);
+        decoder.set_transformations(png::Transformations::EXPAND | png::Transformations::STRIP_16);
+        let mut reader = decoder.read_info()?;
+        let mut buffer = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buffer)?;
+        let bytes = &buffer[..info.buffer_size()];
+
+        let channels = info.color_type.samples();
+        let mut canvas = Canvas::new(info.width as usize, info.height as usize);
+        for (y, row) in bytes.chunks(info.line_size).enumerate() {
+            for x in 0..info.width as usize {
+                let texel = &row[x * channels..(x + 1) * channels];
+                let rgb = match info.color_type {
+                    png::ColorType::Grayscale | png::ColorType::GrayscaleAlpha => {
+                        [texel[0], texel[0], texel[0]]
+                    }
+                    _ => [texel[0], texel[1], texel[2]],
+                };
+                canvas.write_pixel(x, y, Color::from_rgb(rgb));
+            }
+        }
+        Ok(canvas)
+    }
+
     pub fn to_ppm_file(&self, path: &str) -> std::io::Result<()> {
         let mut file = File::create(path)?;
         println!("Writing PPM to {}", path);
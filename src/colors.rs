@@ -75,6 +75,15 @@ impl Color {
         format!("{} {} {}", red, green, blue)
     }
 
+    pub fn from_rgb(rgb: [u8; 3]) -> Color {
+        let max_value = 255f64;
+        Color::new(
+            rgb[0] as f64 / max_value,
+            rgb[1] as f64 / max_value,
+            rgb[2] as f64 / max_value,
+        )
+    }
+
     pub fn to_rgb(&self) -> [u8; 3] {
         let max_value = 255f64;
         let red = (max_value * self.red().clamp(0.0, 1.0)).round() as u8;
@@ -0,0 +1,172 @@
+use std::sync::{Arc, Mutex};
+
+use crate::bounding_box::BoundingBox;
+use crate::intersection::{Intersection, Intersections};
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::scene_object::MutSceneObject;
+use crate::tuple::Tuple;
+use crate::utils::EPSILON;
+use crate::{matrix::Matrix, scene_object::SceneObject};
+use rand::Rng;
+
+// Double-napped cone around the y axis with its tip at the origin, where the radius at any
+// height equals |y|. Can be truncated and capped the same way as a cylinder.
+#[derive(Debug, Clone)]
+pub struct Cone {
+    id: i32,
+    pub transform: Matrix,
+    pub material: Material,
+    pub minimum: f64,
+    pub maximum: f64,
+    pub closed: bool,
+}
+
+// Checks whether the intersection at `t` lies within the radius of a cap at height `y`
+fn check_cap(ray: Ray, t: f64, y: f64) -> bool {
+    let x = ray.origin.x + t * ray.direction.x;
+    let z = ray.origin.z + t * ray.direction.z;
+    x.powi(2) + z.powi(2) <= y.powi(2)
+}
+
+impl Cone {
+    pub fn new() -> Cone {
+        Cone::truncated(f64::NEG_INFINITY, f64::INFINITY, false)
+    }
+
+    pub fn truncated(minimum: f64, maximum: f64, closed: bool) -> Cone {
+        let mut rng = rand::thread_rng();
+        Cone {
+            material: Material::default(),
+            id: rng.gen::<i32>(),
+            transform: Matrix::identity_matrix(4),
+            minimum,
+            maximum,
+            closed,
+        }
+    }
+
+    fn push_if_in_bounds(
+        &self,
+        ray: Ray,
+        t: f64,
+        scene_object: &MutSceneObject,
+        res: &mut Intersections,
+    ) {
+        let y = ray.origin.y + t * ray.direction.y;
+        if self.minimum < y && y < self.maximum {
+            res.push(Arc::new(Intersection::new(t, scene_object.clone())));
+        }
+    }
+
+    fn intersect_caps(&self, ray: Ray, scene_object: MutSceneObject, res: &mut Intersections) {
+        if !self.closed || ray.direction.y.abs() < EPSILON {
+            return;
+        }
+
+        let t = (self.minimum - ray.origin.y) / ray.direction.y;
+        if check_cap(ray, t, self.minimum) {
+            res.push(Arc::new(Intersection::new(t, scene_object.clone())));
+        }
+
+        let t = (self.maximum - ray.origin.y) / ray.direction.y;
+        if check_cap(ray, t, self.maximum) {
+            res.push(Arc::new(Intersection::new(t, scene_object)));
+        }
+    }
+}
+
+impl Default for Cone {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SceneObject for Cone {
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn transformation(&self) -> Matrix {
+        self.transform.clone()
+    }
+
+    fn set_transformation(&mut self, transform: &Matrix) {
+        self.transform = transform.clone();
+    }
+
+    fn material(&self) -> Material {
+        self.material.clone()
+    }
+
+    fn set_material(&mut self, material: &Material) {
+        self.material = material.clone();
+    }
+
+    fn local_intersect(&self, ray: Ray, scene_object: MutSceneObject) -> Intersections {
+        let mut res = Intersections::new();
+
+        let (o, d) = (ray.origin, ray.direction);
+        let a = d.x.powi(2) - d.y.powi(2) + d.z.powi(2);
+        let b = 2.0 * o.x * d.x - 2.0 * o.y * d.y + 2.0 * o.z * d.z;
+        let c = o.x.powi(2) - o.y.powi(2) + o.z.powi(2);
+
+        if a.abs() < EPSILON {
+            // The ray is parallel to one of the halves, so it can cross the other half at
+            // most once. If b is zero as well it misses both halves entirely.
+            if b.abs() >= EPSILON {
+                let t = -c / (2.0 * b);
+                self.push_if_in_bounds(ray, t, &scene_object, &mut res);
+            }
+        } else {
+            let discriminant = b.powi(2) - 4.0 * a * c;
+            if discriminant < 0.0 {
+                return res;
+            }
+
+            let t0 = (-b - discriminant.sqrt()) / (2.0 * a);
+            let t1 = (-b + discriminant.sqrt()) / (2.0 * a);
+
+            for t in [t0.min(t1), t0.max(t1)] {
+                self.push_if_in_bounds(ray, t, &scene_object, &mut res);
+            }
+        }
+
+        self.intersect_caps(ray, scene_object, &mut res);
+        res
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        let limit = self.minimum.abs().max(self.maximum.abs());
+        BoundingBox::new(
+            Tuple::point(-limit, self.minimum, -limit),
+            Tuple::point(limit, self.maximum, limit),
+        )
+    }
+
+    fn local_normal_at(&self, point: Tuple) -> Tuple {
+        let distance = point.x.powi(2) + point.z.powi(2);
+
+        if distance < point.y.powi(2) && point.y >= self.maximum - EPSILON {
+            Tuple::vector(0.0, 1.0, 0.0)
+        } else if distance < point.y.powi(2) && point.y <= self.minimum + EPSILON {
+            Tuple::vector(0.0, -1.0, 0.0)
+        } else {
+            let mut y = distance.sqrt();
+            if point.y > 0.0 {
+                y = -y;
+            }
+            Tuple::vector(point.x, y, point.z)
+        }
+    }
+}
+
+pub fn cone() -> MutSceneObject {
+    Arc::new(Mutex::new(Box::new(Cone::new())))
+}
+
+pub fn truncated_cone(minimum: f64, maximum: f64, closed: bool) -> MutSceneObject {
+    Arc::new(Mutex::new(Box::new(Cone::truncated(
+        minimum, maximum, closed,
+    ))))
+}
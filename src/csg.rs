@@ -0,0 +1,145 @@
+use std::sync::{Arc, Mutex};
+
+use crate::bounding_box::BoundingBox;
+use crate::bvh::BvhStats;
+use crate::intersection::{intersect_with_stats, Intersections};
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::scene_object::MutSceneObject;
+use crate::tuple::Tuple;
+use crate::{matrix::Matrix, scene_object::SceneObject};
+use rand::Rng;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CsgOperation {
+    Union,
+    Intersection,
+    Difference,
+}
+
+// Constructive solid geometry: combines the volumes of two objects
+#[derive(Debug, Clone)]
+pub struct Csg {
+    id: i32,
+    pub transform: Matrix,
+    pub material: Material,
+    pub operation: CsgOperation,
+    pub left: MutSceneObject,
+    pub right: MutSceneObject,
+}
+
+/*
+Decides whether a hit on one of the children is part of the combined surface.
+left_hit: whether the hit is on the left child
+inside_left: whether the hit is inside the left child
+inside_right: whether the hit is inside the right child
+ */
+pub fn intersection_allowed(
+    operation: CsgOperation,
+    left_hit: bool,
+    inside_left: bool,
+    inside_right: bool,
+) -> bool {
+    match operation {
+        CsgOperation::Union => (left_hit && !inside_right) || (!left_hit && !inside_left),
+        CsgOperation::Intersection => (left_hit && inside_right) || (!left_hit && inside_left),
+        CsgOperation::Difference => (left_hit && !inside_right) || (!left_hit && inside_left),
+    }
+}
+
+impl Csg {
+    pub fn new(operation: CsgOperation, left: MutSceneObject, right: MutSceneObject) -> Csg {
+        let mut rng = rand::thread_rng();
+        Csg {
+            material: Material::default(),
+            id: rng.gen::<i32>(),
+            transform: Matrix::identity_matrix(4),
+            operation,
+            left,
+            right,
+        }
+    }
+}
+
+impl SceneObject for Csg {
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn transformation(&self) -> Matrix {
+        self.transform.clone()
+    }
+
+    fn set_transformation(&mut self, transform: &Matrix) {
+        self.transform = transform.clone();
+    }
+
+    fn material(&self) -> Material {
+        self.material.clone()
+    }
+
+    fn set_material(&mut self, material: &Material) {
+        self.material = material.clone();
+    }
+
+    fn local_intersect(&self, ray: Ray, scene_object: MutSceneObject) -> Intersections {
+        self.local_intersect_with_stats(ray, scene_object, &mut BvhStats::default())
+    }
+
+    fn local_intersect_with_stats(
+        &self,
+        ray: Ray,
+        _scene_object: MutSceneObject,
+        stats: &mut BvhStats,
+    ) -> Intersections {
+        // Remember which child every hit came from before merging them into one sorted list
+        let mut hits = intersect_with_stats(self.left.clone(), ray, stats)
+            .values
+            .into_iter()
+            .map(|i| (i, true))
+            .chain(
+                intersect_with_stats(self.right.clone(), ray, stats)
+                    .values
+                    .into_iter()
+                    .map(|i| (i, false)),
+            )
+            .collect::<Vec<_>>();
+        hits.sort_by(|(a, _), (b, _)| a.t.total_cmp(&b.t));
+
+        // Every hit flips whether the ray is inside the child it belongs to
+        let mut inside_left = false;
+        let mut inside_right = false;
+        let mut res = Intersections::new();
+        for (i, left_hit) in hits {
+            if intersection_allowed(self.operation, left_hit, inside_left, inside_right) {
+                res.push(Arc::new(i.with_parent(&self.transform)));
+            }
+
+            if left_hit {
+                inside_left = !inside_left;
+            } else {
+                inside_right = !inside_right;
+            }
+        }
+        res
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        let left = self.left.lock().unwrap().parent_space_bounds();
+        let right = self.right.lock().unwrap().parent_space_bounds();
+        left.merge(&right)
+    }
+
+    fn build_bvh(&mut self) {
+        self.left.lock().unwrap().build_bvh();
+        self.right.lock().unwrap().build_bvh();
+    }
+
+    fn local_normal_at(&self, _point: Tuple) -> Tuple {
+        panic!("CSG objects have no surface of their own, normals come from their children")
+    }
+}
+
+pub fn csg(operation: CsgOperation, left: MutSceneObject, right: MutSceneObject) -> MutSceneObject {
+    Arc::new(Mutex::new(Box::new(Csg::new(operation, left, right))))
+}
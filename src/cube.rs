@@ -0,0 +1,120 @@
+use std::sync::{Arc, Mutex};
+
+use crate::bounding_box::BoundingBox;
+use crate::intersection::{Intersection, Intersections};
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::scene_object::MutSceneObject;
+use crate::tuple::Tuple;
+use crate::utils::EPSILON;
+use crate::{matrix::Matrix, scene_object::SceneObject};
+use rand::Rng;
+
+// Axis-aligned cube spanning -1..1 on every axis in object space
+#[derive(Debug, Clone)]
+pub struct Cube {
+    id: i32,
+    pub transform: Matrix,
+    pub material: Material,
+}
+
+// Returns where the ray enters and leaves the slab between -1 and 1 on a single axis
+fn check_axis(origin: f64, direction: f64) -> (f64, f64) {
+    let tmin_numerator = -1.0 - origin;
+    let tmax_numerator = 1.0 - origin;
+
+    let (tmin, tmax) = if direction.abs() >= EPSILON {
+        (tmin_numerator / direction, tmax_numerator / direction)
+    } else {
+        (
+            tmin_numerator * f64::INFINITY,
+            tmax_numerator * f64::INFINITY,
+        )
+    };
+
+    if tmin > tmax {
+        (tmax, tmin)
+    } else {
+        (tmin, tmax)
+    }
+}
+
+impl SceneObject for Cube {
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn transformation(&self) -> Matrix {
+        self.transform.clone()
+    }
+
+    fn set_transformation(&mut self, transform: &Matrix) {
+        self.transform = transform.clone();
+    }
+
+    fn material(&self) -> Material {
+        self.material.clone()
+    }
+
+    fn set_material(&mut self, material: &Material) {
+        self.material = material.clone();
+    }
+
+    fn local_intersect(&self, ray: Ray, scene_object: MutSceneObject) -> Intersections {
+        let mut res = Intersections::new();
+
+        let (xtmin, xtmax) = check_axis(ray.origin.x, ray.direction.x);
+        let (ytmin, ytmax) = check_axis(ray.origin.y, ray.direction.y);
+        let (ztmin, ztmax) = check_axis(ray.origin.z, ray.direction.z);
+
+        // The ray is inside the cube only while it is inside all three slabs at once
+        let tmin = xtmin.max(ytmin).max(ztmin);
+        let tmax = xtmax.min(ytmax).min(ztmax);
+
+        if tmin > tmax {
+            return res;
+        }
+
+        res.push(Arc::new(Intersection::new(tmin, scene_object.clone())));
+        res.push(Arc::new(Intersection::new(tmax, scene_object)));
+        res
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::new(Tuple::point(-1.0, -1.0, -1.0), Tuple::point(1.0, 1.0, 1.0))
+    }
+
+    fn local_normal_at(&self, point: Tuple) -> Tuple {
+        // The face the point lies on is the one for the component with the largest magnitude
+        let maxc = point.x.abs().max(point.y.abs()).max(point.z.abs());
+
+        if maxc == point.x.abs() {
+            Tuple::vector(point.x, 0.0, 0.0)
+        } else if maxc == point.y.abs() {
+            Tuple::vector(0.0, point.y, 0.0)
+        } else {
+            Tuple::vector(0.0, 0.0, point.z)
+        }
+    }
+}
+
+impl Cube {
+    pub fn new() -> Cube {
+        let mut rng = rand::thread_rng();
+        Cube {
+            material: Material::default(),
+            id: rng.gen::<i32>(),
+            transform: Matrix::identity_matrix(4),
+        }
+    }
+}
+
+impl Default for Cube {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn cube() -> MutSceneObject {
+    Arc::new(Mutex::new(Box::new(Cube::new())))
+}
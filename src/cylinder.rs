@@ -0,0 +1,152 @@
+use std::sync::{Arc, Mutex};
+
+use crate::bounding_box::BoundingBox;
+use crate::intersection::{Intersection, Intersections};
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::scene_object::MutSceneObject;
+use crate::tuple::Tuple;
+use crate::utils::EPSILON;
+use crate::{matrix::Matrix, scene_object::SceneObject};
+use rand::Rng;
+
+// Cylinder of radius 1 around the y axis, optionally truncated between `minimum` and
+// `maximum` (exclusive) and capped at both ends when `closed` is set
+#[derive(Debug, Clone)]
+pub struct Cylinder {
+    id: i32,
+    pub transform: Matrix,
+    pub material: Material,
+    pub minimum: f64,
+    pub maximum: f64,
+    pub closed: bool,
+}
+
+// Checks whether the intersection at `t` lies within the radius of a cap
+fn check_cap(ray: Ray, t: f64) -> bool {
+    let x = ray.origin.x + t * ray.direction.x;
+    let z = ray.origin.z + t * ray.direction.z;
+    x.powi(2) + z.powi(2) <= 1.0
+}
+
+impl Cylinder {
+    pub fn new() -> Cylinder {
+        Cylinder::truncated(f64::NEG_INFINITY, f64::INFINITY, false)
+    }
+
+    pub fn truncated(minimum: f64, maximum: f64, closed: bool) -> Cylinder {
+        let mut rng = rand::thread_rng();
+        Cylinder {
+            material: Material::default(),
+            id: rng.gen::<i32>(),
+            transform: Matrix::identity_matrix(4),
+            minimum,
+            maximum,
+            closed,
+        }
+    }
+
+    fn intersect_caps(&self, ray: Ray, scene_object: MutSceneObject, res: &mut Intersections) {
+        // Caps only matter if the cylinder is closed and the ray can actually reach them
+        if !self.closed || ray.direction.y.abs() < EPSILON {
+            return;
+        }
+
+        let t = (self.minimum - ray.origin.y) / ray.direction.y;
+        if check_cap(ray, t) {
+            res.push(Arc::new(Intersection::new(t, scene_object.clone())));
+        }
+
+        let t = (self.maximum - ray.origin.y) / ray.direction.y;
+        if check_cap(ray, t) {
+            res.push(Arc::new(Intersection::new(t, scene_object)));
+        }
+    }
+}
+
+impl Default for Cylinder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SceneObject for Cylinder {
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn transformation(&self) -> Matrix {
+        self.transform.clone()
+    }
+
+    fn set_transformation(&mut self, transform: &Matrix) {
+        self.transform = transform.clone();
+    }
+
+    fn material(&self) -> Material {
+        self.material.clone()
+    }
+
+    fn set_material(&mut self, material: &Material) {
+        self.material = material.clone();
+    }
+
+    fn local_intersect(&self, ray: Ray, scene_object: MutSceneObject) -> Intersections {
+        let mut res = Intersections::new();
+
+        let a = ray.direction.x.powi(2) + ray.direction.z.powi(2);
+
+        // A ray parallel to the y axis can only hit the caps
+        if a.abs() >= EPSILON {
+            let b = 2.0 * ray.origin.x * ray.direction.x + 2.0 * ray.origin.z * ray.direction.z;
+            let c = ray.origin.x.powi(2) + ray.origin.z.powi(2) - 1.0;
+
+            let discriminant = b.powi(2) - 4.0 * a * c;
+            if discriminant < 0.0 {
+                return res;
+            }
+
+            let t0 = (-b - discriminant.sqrt()) / (2.0 * a);
+            let t1 = (-b + discriminant.sqrt()) / (2.0 * a);
+
+            for t in [t0.min(t1), t0.max(t1)] {
+                let y = ray.origin.y + t * ray.direction.y;
+                if self.minimum < y && y < self.maximum {
+                    res.push(Arc::new(Intersection::new(t, scene_object.clone())));
+                }
+            }
+        }
+
+        self.intersect_caps(ray, scene_object, &mut res);
+        res
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::new(
+            Tuple::point(-1.0, self.minimum, -1.0),
+            Tuple::point(1.0, self.maximum, 1.0),
+        )
+    }
+
+    fn local_normal_at(&self, point: Tuple) -> Tuple {
+        let distance = point.x.powi(2) + point.z.powi(2);
+
+        if distance < 1.0 && point.y >= self.maximum - EPSILON {
+            Tuple::vector(0.0, 1.0, 0.0)
+        } else if distance < 1.0 && point.y <= self.minimum + EPSILON {
+            Tuple::vector(0.0, -1.0, 0.0)
+        } else {
+            Tuple::vector(point.x, 0.0, point.z)
+        }
+    }
+}
+
+pub fn cylinder() -> MutSceneObject {
+    Arc::new(Mutex::new(Box::new(Cylinder::new())))
+}
+
+pub fn truncated_cylinder(minimum: f64, maximum: f64, closed: bool) -> MutSceneObject {
+    Arc::new(Mutex::new(Box::new(Cylinder::truncated(
+        minimum, maximum, closed,
+    ))))
+}
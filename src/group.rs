@@ -0,0 +1,129 @@
+use std::sync::{Arc, Mutex};
+
+use crate::bounding_box::BoundingBox;
+use crate::bvh::{Bvh, BvhStats};
+use crate::intersection::{intersect_with_stats, Intersections};
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::scene_object::MutSceneObject;
+use crate::tuple::Tuple;
+use crate::{matrix::Matrix, scene_object::SceneObject};
+use rand::Rng;
+
+// Collection of objects sharing a transform. Children's transforms are relative to the group,
+// so moving the group moves everything in it. Children are always shaded with their own
+// materials, so a material set on the group is only stored, not used for rendering.
+#[derive(Debug, Clone)]
+pub struct Group {
+    id: i32,
+    pub transform: Matrix,
+    pub material: Material, // not handed down to the children or used for shading
+    pub children: Vec<MutSceneObject>,
+    // Built by `build_bvh`, until then every child is tested against every ray. Ignored once
+    // children have been added, removed or replaced since it was built.
+    pub bvh: Option<Bvh>,
+}
+
+impl Group {
+    pub fn new() -> Group {
+        let mut rng = rand::thread_rng();
+        Group {
+            material: Material::default(),
+            id: rng.gen::<i32>(),
+            transform: Matrix::identity_matrix(4),
+            children: Vec::new(),
+            bvh: None,
+        }
+    }
+
+    pub fn add_child(&mut self, child: MutSceneObject) -> &mut Self {
+        self.children.push(child);
+        self.bvh = None;
+        self
+    }
+}
+
+impl Default for Group {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl SceneObject for Group {
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn transformation(&self) -> Matrix {
+        self.transform.clone()
+    }
+
+    fn set_transformation(&mut self, transform: &Matrix) {
+        self.transform = transform.clone();
+    }
+
+    fn material(&self) -> Material {
+        self.material.clone()
+    }
+
+    // Only stores the material, set materials on the children to change how they look
+    fn set_material(&mut self, material: &Material) {
+        self.material = material.clone();
+    }
+
+    fn local_intersect(&self, ray: Ray, scene_object: MutSceneObject) -> Intersections {
+        self.local_intersect_with_stats(ray, scene_object, &mut BvhStats::default())
+    }
+
+    fn local_intersect_with_stats(
+        &self,
+        ray: Ray,
+        _scene_object: MutSceneObject,
+        stats: &mut BvhStats,
+    ) -> Intersections {
+        let child_hits = match &self.bvh {
+            Some(bvh) if bvh.is_built_over(&self.children) => bvh.intersect_with_stats(ray, stats),
+            // No BVH yet, or `children` has changed since it was built
+            _ => {
+                let mut res = Intersections::new();
+                for child in self.children.iter() {
+                    stats.objects_tested += 1;
+                    res.concat(intersect_with_stats(child.clone(), ray, stats));
+                }
+                res
+            }
+        };
+
+        let mut res = Intersections::new();
+        for i in child_hits.values.iter() {
+            res.push(Arc::new(i.with_parent(&self.transform)));
+        }
+        res.sort();
+        res
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        self.children
+            .iter()
+            .fold(BoundingBox::empty(), |acc, child| {
+                acc.merge(&child.lock().unwrap().parent_space_bounds())
+            })
+    }
+
+    fn build_bvh(&mut self) {
+        for child in self.children.iter() {
+            child.lock().unwrap().build_bvh();
+        }
+        self.bvh = Some(Bvh::new(self.children.clone()));
+    }
+
+    fn local_normal_at(&self, _point: Tuple) -> Tuple {
+        panic!("Groups have no surface of their own, normals come from their children")
+    }
+}
+
+pub fn group(children: Vec<MutSceneObject>) -> MutSceneObject {
+    let mut g = Group::new();
+    g.children = children;
+    Arc::new(Mutex::new(Box::new(g)))
+}
@@ -0,0 +1,307 @@
+use std::sync::{Arc, Mutex};
+
+use crate::bounding_box::BoundingBox;
+use crate::canvas::Canvas;
+use crate::intersection::{Intersection, Intersections};
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::scene_object::MutSceneObject;
+use crate::triangle::moller_trumbore;
+use crate::tuple::Tuple;
+use crate::utils::EPSILON;
+use crate::{matrix::Matrix, scene_object::SceneObject};
+use rand::Rng;
+
+/*
+Terrain over the unit square in x and z, rising to the given height at each sample of a grid.
+The cell between every four neighbouring samples is split into two triangles, and rays are
+walked through the cells they cross instead of being tested against every triangle.
+
+Internally rays are intersected in grid space, where every cell is one unit wide, so tiny
+cells of large images don't run into precision trouble.
+*/
+#[derive(Debug, Clone)]
+pub struct HeightField {
+    id: i32,
+    pub transform: Matrix,
+    pub material: Material,
+    heights: Vec<Vec<f64>>, // heights[row][column], with rows along z and columns along x
+    normals: Vec<Vec<Tuple>>, // smoothed normal at every sample, in object space
+    min_height: f64,
+    max_height: f64,
+}
+
+impl HeightField {
+    // Needs at least 2x2 samples, with every row the same length
+    pub fn new(heights: Vec<Vec<f64>>) -> HeightField {
+        assert!(
+            heights.len() >= 2 && heights[0].len() >= 2,
+            "Height field needs at least 2x2 samples"
+        );
+        assert!(
+            heights.iter().all(|row| row.len() == heights[0].len()),
+            "Height field rows differ in length"
+        );
+
+        let mut rng = rand::thread_rng();
+        let all = heights.iter().flatten();
+        let min_height = all.clone().cloned().fold(f64::INFINITY, f64::min);
+        let max_height = all.cloned().fold(f64::NEG_INFINITY, f64::max);
+        let normals = sample_normals(&heights);
+        HeightField {
+            id: rng.gen::<i32>(),
+            transform: Matrix::identity_matrix(4),
+            material: Material::default(),
+            heights,
+            normals,
+            min_height,
+            max_height,
+        }
+    }
+
+    // Heights from the brightness of an image, from 0 for black up to `vertical_scale` for
+    // white. The top row of the image ends up at z = 0.
+    pub fn from_canvas(canvas: &Canvas, vertical_scale: f64) -> HeightField {
+        let heights = canvas
+            .pixels
+            .iter()
+            .map(|row| {
+                row.iter()
+                    .map(|c| (c.red() + c.green() + c.blue()) / 3.0 * vertical_scale)
+                    .collect()
+            })
+            .collect();
+        HeightField::new(heights)
+    }
+
+    pub fn from_png_file(
+        path: &str,
+        vertical_scale: f64,
+    ) -> Result<HeightField, png::DecodingError> {
+        Ok(HeightField::from_canvas(
+            &Canvas::from_png_file(path)?,
+            vertical_scale,
+        ))
+    }
+
+    pub fn columns(&self) -> usize {
+        self.heights[0].len()
+    }
+
+    pub fn rows(&self) -> usize {
+        self.heights.len()
+    }
+
+    pub fn height_at(&self, column: usize, row: usize) -> f64 {
+        self.heights[row][column]
+    }
+
+    // Sample position in grid space
+    fn vertex(&self, column: usize, row: usize) -> Tuple {
+        Tuple::point(column as f64, self.heights[row][column], row as f64)
+    }
+
+    // Adds the t of every hit with the two triangles of a cell
+    fn intersect_cell(&self, ray: Ray, column: usize, row: usize, hits: &mut Vec<f64>) {
+        let v00 = self.vertex(column, row);
+        let v10 = self.vertex(column + 1, row);
+        let v01 = self.vertex(column, row + 1);
+        let v11 = self.vertex(column + 1, row + 1);
+
+        for (p1, p2, p3) in [(v00, v10, v01), (v11, v01, v10)] {
+            if let Some((t, _, _)) = moller_trumbore(ray, p1, p2.sub(p1), p3.sub(p1)) {
+                // A ray right through an edge hits the triangles on both sides of it
+                if hits.last().is_none_or(|last| (t - last).abs() > EPSILON) {
+                    hits.push(t);
+                }
+            }
+        }
+    }
+
+    // Whether the ray is anywhere between the lowest and highest corner of the cell while
+    // it's above the cell, between t_enter and t_exit
+    fn cell_may_be_hit(
+        &self,
+        ray: Ray,
+        column: usize,
+        row: usize,
+        t_enter: f64,
+        t_exit: f64,
+    ) -> bool {
+        let corners = [
+            self.heights[row][column],
+            self.heights[row][column + 1],
+            self.heights[row + 1][column],
+            self.heights[row + 1][column + 1],
+        ];
+        let lowest = corners.iter().cloned().fold(f64::INFINITY, f64::min);
+        let highest = corners.iter().cloned().fold(f64::NEG_INFINITY, f64::max);
+
+        let y1 = ray.origin.y + ray.direction.y * t_enter;
+        let y2 = ray.origin.y + ray.direction.y * t_exit;
+        y1.min(y2) <= highest + EPSILON && y1.max(y2) >= lowest - EPSILON
+    }
+}
+
+/*
+Normals from the slope between each sample's neighbours, or between the sample and its only
+neighbour along the edges. Slopes are taken in object space, where the whole grid spans one unit.
+*/
+fn sample_normals(heights: &[Vec<f64>]) -> Vec<Vec<Tuple>> {
+    let rows = heights.len();
+    let columns = heights[0].len();
+
+    (0..rows)
+        .map(|row| {
+            (0..columns)
+                .map(|column| {
+                    let (left, right) = (column.saturating_sub(1), (column + 1).min(columns - 1));
+                    let (back, front) = (row.saturating_sub(1), (row + 1).min(rows - 1));
+                    let dx = (heights[row][right] - heights[row][left]) * (columns - 1) as f64
+                        / (right - left) as f64;
+                    let dz = (heights[front][column] - heights[back][column]) * (rows - 1) as f64
+                        / (front - back) as f64;
+                    Tuple::vector(-dx, 1.0, -dz).normalize()
+                })
+                .collect()
+        })
+        .collect()
+}
+
+// Where along the ray it next crosses a cell boundary on one axis, and how far apart the
+// crossings are. Never, for rays that don't move along the axis.
+fn first_crossing(origin: f64, direction: f64, cell: i64) -> (i64, f64, f64) {
+    if direction > 0.0 {
+        (1, (cell as f64 + 1.0 - origin) / direction, 1.0 / direction)
+    } else if direction < 0.0 {
+        (-1, (cell as f64 - origin) / direction, -1.0 / direction)
+    } else {
+        (0, f64::INFINITY, f64::INFINITY)
+    }
+}
+
+impl SceneObject for HeightField {
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn transformation(&self) -> Matrix {
+        self.transform.clone()
+    }
+
+    fn set_transformation(&mut self, transform: &Matrix) {
+        self.transform = transform.clone();
+    }
+
+    fn material(&self) -> Material {
+        self.material.clone()
+    }
+
+    fn set_material(&mut self, material: &Material) {
+        self.material = material.clone();
+    }
+
+    fn local_intersect(&self, ray: Ray, scene_object: MutSceneObject) -> Intersections {
+        let mut res = Intersections::new();
+        let (t_enter, t_exit) = match self.bounds().intersection_range(ray) {
+            None => return res,
+            Some(range) => range,
+        };
+
+        // Scaling the ray into grid space leaves its t values unchanged
+        let cells_x = (self.columns() - 1) as i64;
+        let cells_z = (self.rows() - 1) as i64;
+        let ray = Ray::new(
+            Tuple::point(
+                ray.origin.x * cells_x as f64,
+                ray.origin.y,
+                ray.origin.z * cells_z as f64,
+            ),
+            Tuple::vector(
+                ray.direction.x * cells_x as f64,
+                ray.direction.y,
+                ray.direction.z * cells_z as f64,
+            ),
+        );
+
+        // Walk the cells from the one where the ray enters the field to the one where it leaves
+        let start = ray.position(t_enter);
+        let mut column = (start.x.floor() as i64).clamp(0, cells_x - 1);
+        let mut row = (start.z.floor() as i64).clamp(0, cells_z - 1);
+        let (step_x, mut next_x, delta_x) = first_crossing(ray.origin.x, ray.direction.x, column);
+        let (step_z, mut next_z, delta_z) = first_crossing(ray.origin.z, ray.direction.z, row);
+
+        let mut hits = Vec::new();
+        let mut t = t_enter;
+        loop {
+            let t_leave = next_x.min(next_z).min(t_exit);
+            if self.cell_may_be_hit(ray, column as usize, row as usize, t, t_leave) {
+                self.intersect_cell(ray, column as usize, row as usize, &mut hits);
+            }
+            if t_leave >= t_exit {
+                break;
+            }
+
+            if next_x < next_z {
+                column += step_x;
+                t = next_x;
+                next_x += delta_x;
+            } else {
+                row += step_z;
+                t = next_z;
+                next_z += delta_z;
+            }
+            if !(0..cells_x).contains(&column) || !(0..cells_z).contains(&row) {
+                break;
+            }
+        }
+
+        for t in hits {
+            res.push(Arc::new(Intersection::new(t, scene_object.clone())));
+        }
+        res
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::new(
+            Tuple::point(0.0, self.min_height, 0.0),
+            Tuple::point(1.0, self.max_height, 1.0),
+        )
+    }
+
+    // Interpolates the sample normals across the triangle the point is on
+    fn local_normal_at(&self, point: Tuple) -> Tuple {
+        let cells_x = self.columns() - 1;
+        let cells_z = self.rows() - 1;
+        let x = (point.x * cells_x as f64).clamp(0.0, cells_x as f64);
+        let z = (point.z * cells_z as f64).clamp(0.0, cells_z as f64);
+        let column = (x.floor() as usize).min(cells_x - 1);
+        let row = (z.floor() as usize).min(cells_z - 1);
+        let (fx, fz) = (x - column as f64, z - row as f64);
+
+        let n00 = self.normals[row][column];
+        let n10 = self.normals[row][column + 1];
+        let n01 = self.normals[row + 1][column];
+        let n11 = self.normals[row + 1][column + 1];
+        let normal = if fx + fz <= 1.0 {
+            n00.scalar_mul(1.0 - fx - fz)
+                .add(n10.scalar_mul(fx))
+                .add(n01.scalar_mul(fz))
+        } else {
+            n11.scalar_mul(fx + fz - 1.0)
+                .add(n01.scalar_mul(1.0 - fx))
+                .add(n10.scalar_mul(1.0 - fz))
+        };
+        normal.normalize()
+    }
+}
+
+impl Default for HeightField {
+    fn default() -> Self {
+        Self::new(vec![vec![0.0; 2]; 2])
+    }
+}
+
+pub fn height_field(heights: Vec<Vec<f64>>) -> MutSceneObject {
+    Arc::new(Mutex::new(Box::new(HeightField::new(heights))))
+}
@@ -1,7 +1,10 @@
+use crate::bvh::BvhStats;
 use crate::colors::Color;
-use crate::light::lighting;
+use crate::light::{lighting, Light};
 use std::{ops::Index, sync::Arc};
 
+use crate::material::Material;
+use crate::matrix::Matrix;
 use crate::ray::Ray;
 use crate::scene_object::MutSceneObject;
 use crate::tuple::Tuple;
@@ -12,17 +15,29 @@ use crate::world::World;
 pub struct Intersection {
     pub t: f64,
     pub scene_object: MutSceneObject,
+    // Barycentric coordinates of the hit, only meaningful for triangles
+    pub u: f64,
+    pub v: f64,
+    // Combined transform of all the groups the object was reached through, outermost first
+    pub parent_transform: Option<Matrix>,
 }
 
 #[derive(Debug)]
 pub struct IntersectionPrecomputations {
     pub t: f64,
     pub scene_object: MutSceneObject,
-    pub point: Tuple,      // in world space
-    pub over_point: Tuple, // To adjust for floating point precision loss when calculating shadows
-    pub eye_vector: Tuple, // pointing back towards the eye
+    pub point: Tuple,        // in world space
+    pub object_point: Tuple, // same point in the space of the hit object
+    pub over_point: Tuple,   // To adjust for floating point precision loss when calculating shadows
+    pub under_point: Tuple,  // Same, but just below the surface where refracted rays start
+    pub eye_vector: Tuple,   // pointing back towards the eye
     pub normal_vector: Tuple,
+    pub reflect_vector: Tuple, // incoming ray mirrored around the normal
     pub is_inside_object: bool,
+    pub n1: f64,           // refractive index of the material the ray comes from
+    pub n2: f64,           // and of the one it enters
+    pub absorption: Color, // absorption of the material the refracted ray travels through
+    pub reflected_absorption: Color, // and of the one the reflected ray travels back through
 }
 
 impl PartialEq for Intersection {
@@ -41,7 +56,32 @@ impl PartialEq for Intersection {
 
 impl Intersection {
     pub fn new(t: f64, scene_object: MutSceneObject) -> Intersection {
-        Intersection { t, scene_object }
+        Intersection::new_with_uv(t, scene_object, 0.0, 0.0)
+    }
+
+    pub fn new_with_uv(t: f64, scene_object: MutSceneObject, u: f64, v: f64) -> Intersection {
+        Intersection {
+            t,
+            scene_object,
+            u,
+            v,
+            parent_transform: None,
+        }
+    }
+
+    // Copy of this intersection as seen from a group with the given transform
+    pub fn with_parent(&self, parent_transform: &Matrix) -> Intersection {
+        let parent_transform = match &self.parent_transform {
+            Some(inner) => parent_transform.mat_mul(inner),
+            None => parent_transform.clone(),
+        };
+        Intersection {
+            t: self.t,
+            scene_object: self.scene_object.clone(),
+            u: self.u,
+            v: self.v,
+            parent_transform: Some(parent_transform),
+        }
     }
 }
 
@@ -57,6 +97,12 @@ impl Index<usize> for Intersections {
     }
 }
 
+impl From<Vec<Arc<Intersection>>> for Intersections {
+    fn from(values: Vec<Arc<Intersection>>) -> Self {
+        Intersections { values }
+    }
+}
+
 impl Intersections {
     pub fn new() -> Intersections {
         Intersections { values: vec![] }
@@ -90,31 +136,61 @@ impl Intersections {
 }
 
 pub fn intersect(so: MutSceneObject, r: Ray) -> Intersections {
-    let mut tf = so.lock().unwrap().transformation();
-    tf = tf.invert().unwrap();
-    let r = r.transform(&tf);
-    r.intersect(so)
+    intersect_with_stats(so, r, &mut BvhStats::default())
+}
 
-    // so.get_mut().unwrap().set_transformation()
+// Same as `intersect`, also adding up the work done by BVHs inside the object into `stats`
+pub fn intersect_with_stats(so: MutSceneObject, r: Ray, stats: &mut BvhStats) -> Intersections {
+    let guard = so.lock().unwrap();
+    let tf = guard.transformation().invert().unwrap();
+    let local_ray = r.transform(&tf);
+    guard.local_intersect_with_stats(local_ray, so.clone(), stats)
 }
 
 pub fn intersect_world(world: Arc<World>, r: Ray) -> Intersections {
-    let mut res = Intersections::new();
-    for obj in world.objects.iter() {
-        res.concat(intersect(obj.clone(), r));
-    }
+    intersect_world_with_stats(world, r, &mut BvhStats::default())
+}
+
+// Same as `intersect_world`, also counting the BVH nodes visited and objects tested for the
+// ray, down through the BVHs of groups
+pub fn intersect_world_with_stats(
+    world: Arc<World>,
+    r: Ray,
+    stats: &mut BvhStats,
+) -> Intersections {
+    let mut res = match &world.bvh {
+        Some(bvh) if bvh.is_built_over(&world.objects) => bvh.intersect_with_stats(r, stats),
+        // No BVH yet, or `objects` has changed since it was built
+        _ => {
+            let mut res = Intersections::new();
+            for obj in world.objects.iter() {
+                stats.objects_tested += 1;
+                res.concat(intersect_with_stats(obj.clone(), r, stats));
+            }
+            res
+        }
+    };
     res.sort();
     res
 }
 
+// `intersections` are all the sorted intersections along the ray, which are needed to know which
+// objects the hit is inside of
 pub fn prepare_computations(
     intersection: Arc<Intersection>,
     ray: Ray,
+    intersections: &Intersections,
 ) -> IntersectionPrecomputations {
     let point = ray.position(intersection.t);
 
     let eye_vector = ray.direction.negate();
-    let mut normal_vector = intersection.scene_object.lock().unwrap().normal_at(point);
+    let (object_point, mut normal_vector) = {
+        let scene_object = intersection.scene_object.lock().unwrap();
+        (
+            scene_object.world_to_object(point, &intersection),
+            scene_object.normal_at_hit(point, &intersection),
+        )
+    };
 
     let mut is_inside_object = false;
 
@@ -124,52 +200,292 @@ pub fn prepare_computations(
     }
 
     let over_point = point.add(normal_vector.scalar_mul(EPSILON));
+    let under_point = point.sub(normal_vector.scalar_mul(EPSILON));
+    let reflect_vector = ray.direction.reflect(normal_vector);
+    let (from, to) = media_at_hit(&intersection, intersections);
+    let medium = |object: Option<MutSceneObject>| match object {
+        None => Material::default(),
+        Some(object) => object.lock().unwrap().material(),
+    };
+    let from = medium(from);
+    let (n1, reflected_absorption) = (from.refractive_index, from.absorption);
+    let to = medium(to);
+    let (n2, absorption) = (to.refractive_index, to.absorption);
 
     IntersectionPrecomputations {
         t: intersection.t,
         scene_object: intersection.scene_object.clone(),
         point,
+        object_point,
         eye_vector,
         normal_vector,
+        reflect_vector,
         is_inside_object,
         over_point,
+        under_point,
+        n1,
+        n2,
+        absorption,
+        reflected_absorption,
     }
 }
 
-pub fn shade_hit(world: Arc<World>, precomputed: &IntersectionPrecomputations) -> Color {
-    let shadowed = is_shadowed(world.clone(), precomputed.over_point);
+/*
+Walks the intersections up to the hit, keeping the objects the ray is currently inside of. The
+ray leaves the last of them when it reaches the hit and enters the next one, so those are the
+media on either side of the surface. None is empty space.
+*/
+fn media_at_hit(
+    hit: &Intersection,
+    intersections: &Intersections,
+) -> (Option<MutSceneObject>, Option<MutSceneObject>) {
+    let mut containers: Vec<&Intersection> = Vec::new();
+    let mut from = None;
+    for i in intersections.values.iter() {
+        let is_hit = i.t == hit.t && same_instance(i, hit);
+        if is_hit {
+            from = containers.last().map(|c| c.scene_object.clone());
+        }
 
-    lighting(
-        &precomputed.scene_object.lock().unwrap().material(),
-        &world.light_sources[0],
-        precomputed.point,
-        precomputed.eye_vector,
-        precomputed.normal_vector,
-        shadowed,
-    )
+        match containers.iter().position(|c| same_instance(c, i)) {
+            Some(index) => {
+                containers.remove(index);
+            }
+            None => containers.push(i),
+        }
+
+        if is_hit {
+            break;
+        }
+    }
+
+    (from, containers.last().map(|c| c.scene_object.clone()))
 }
 
-pub fn color_at(world: Arc<World>, ray: Ray) -> Color {
+// Whether both hits are on the same instance of a shape, which is the same shape reached
+// through groups with the same transforms. A shape shared between groups is a separate object
+// in each of them.
+fn same_instance(a: &Intersection, b: &Intersection) -> bool {
+    Arc::ptr_eq(&a.scene_object, &b.scene_object) && a.parent_transform == b.parent_transform
+}
+
+// `remaining` is how many more bounces reflected rays may take before they are cut off
+pub fn shade_hit(
+    world: Arc<World>,
+    precomputed: &IntersectionPrecomputations,
+    remaining: usize,
+) -> Color {
+    let material = precomputed.scene_object.lock().unwrap().material();
+
+    // Every light contributes separately, each with its own shadow test
+    let surface = world
+        .light_sources
+        .iter()
+        .fold(Color::new(0.0, 0.0, 0.0), |acc, light| {
+            let visibility = is_shadowed(world.clone(), light.as_ref(), precomputed.over_point);
+            acc.add(lighting(
+                &material,
+                light.as_ref(),
+                precomputed.point,
+                precomputed.object_point,
+                precomputed.eye_vector,
+                precomputed.normal_vector,
+                visibility,
+            ))
+        })
+        .add(material.emission);
+
+    let reflected = reflected_color(world.clone(), precomputed, remaining);
+    let refracted = refracted_color(world, precomputed, remaining);
+
+    // Transparent and reflective surfaces like glass reflect more at grazing angles
+    if material.reflective > 0.0 && material.transparency > 0.0 {
+        let reflectance = schlick(precomputed);
+        surface
+            .add(reflected.scalar_mul(reflectance))
+            .add(refracted.scalar_mul(1.0 - reflectance))
+    } else {
+        surface.add(reflected).add(refracted)
+    }
+}
+
+pub fn color_at(world: Arc<World>, ray: Ray, remaining: usize) -> Color {
     let intersections = intersect_world(world.clone(), ray);
     match intersections.hit() {
         None => Color::new(0.0, 0.0, 0.0),
         Some(intersection) => {
-            let precomputed = prepare_computations(intersection, ray);
-            shade_hit(world.clone(), &precomputed)
+            let precomputed = prepare_computations(intersection, ray, &intersections);
+            shade_hit(world.clone(), &precomputed, remaining)
         }
     }
 }
 
-pub fn is_shadowed(world: Arc<World>, point: Tuple) -> bool {
-    let v = world.light_sources[0].position.sub(point);
-    let distance = v.magnitude();
-    let direction = v.normalize();
+// Color seen in the mirror direction, scaled by how reflective the surface is. Black once the
+// bounces run out, so mirrors facing each other don't recurse forever.
+pub fn reflected_color(
+    world: Arc<World>,
+    precomputed: &IntersectionPrecomputations,
+    remaining: usize,
+) -> Color {
+    let reflective = precomputed
+        .scene_object
+        .lock()
+        .unwrap()
+        .material()
+        .reflective;
+    if remaining == 0 || reflective == 0.0 {
+        return Color::new(0.0, 0.0, 0.0);
+    }
+
+    // Reflections on the inside of an object travel back through it, so they get absorbed too
+    let reflect_ray = Ray::new(precomputed.over_point, precomputed.reflect_vector);
+    absorbed_color_at(
+        world,
+        reflect_ray,
+        precomputed.reflected_absorption,
+        remaining - 1,
+    )
+    .scalar_mul(reflective)
+}
+
+// Color seen through a transparent surface, bent by Snell's law. Black when the bounces run out
+// or under total internal reflection, where all of the light is reflected instead.
+pub fn refracted_color(
+    world: Arc<World>,
+    precomputed: &IntersectionPrecomputations,
+    remaining: usize,
+) -> Color {
+    let transparency = precomputed
+        .scene_object
+        .lock()
+        .unwrap()
+        .material()
+        .transparency;
+    if remaining == 0 || transparency == 0.0 {
+        return Color::new(0.0, 0.0, 0.0);
+    }
+
+    let n_ratio = precomputed.n1 / precomputed.n2;
+    let cos_i = precomputed.eye_vector.dot(precomputed.normal_vector);
+    let sin2_t = n_ratio.powi(2) * (1.0 - cos_i.powi(2));
+    if sin2_t > 1.0 {
+        return Color::new(0.0, 0.0, 0.0);
+    }
 
-    let r = Ray::new(point, direction);
-    let intersections = intersect_world(world.clone(), r);
+    let cos_t = (1.0 - sin2_t).sqrt();
+    let direction = precomputed
+        .normal_vector
+        .scalar_mul(n_ratio * cos_i - cos_t)
+        .sub(precomputed.eye_vector.scalar_mul(n_ratio));
+    let refract_ray = Ray::new(precomputed.under_point, direction);
+
+    absorbed_color_at(world, refract_ray, precomputed.absorption, remaining - 1)
+        .scalar_mul(transparency)
+}
 
+// Like color_at, but dimmed by the medium the ray travels through on the way to the next hit
+fn absorbed_color_at(world: Arc<World>, ray: Ray, absorption: Color, remaining: usize) -> Color {
+    let intersections = intersect_world(world.clone(), ray);
     match intersections.hit() {
-        None => false,
-        Some(intersection) => intersection.t < distance,
+        None => Color::new(0.0, 0.0, 0.0),
+        Some(intersection) => {
+            let distance = intersection.t;
+            let precomputed = prepare_computations(intersection, ray, &intersections);
+            shade_hit(world, &precomputed, remaining).mul(beer_lambert(absorption, distance))
+        }
+    }
+}
+
+// Fraction of each color channel left after traveling `distance` through a medium that absorbs
+// `absorption` of it per unit
+fn beer_lambert(absorption: Color, distance: f64) -> Color {
+    Color::new(
+        (-absorption.red() * distance).exp(),
+        (-absorption.green() * distance).exp(),
+        (-absorption.blue() * distance).exp(),
+    )
+}
+
+// Schlick's approximation of the Fresnel reflectance: the fraction of light that is reflected
+// rather than refracted
+pub fn schlick(precomputed: &IntersectionPrecomputations) -> f64 {
+    let mut cos = precomputed.eye_vector.dot(precomputed.normal_vector);
+
+    if precomputed.n1 > precomputed.n2 {
+        let n = precomputed.n1 / precomputed.n2;
+        let sin2_t = n.powi(2) * (1.0 - cos.powi(2));
+        if sin2_t > 1.0 {
+            return 1.0;
+        }
+        cos = (1.0 - sin2_t).sqrt();
     }
+
+    let r0 = ((precomputed.n1 - precomputed.n2) / (precomputed.n1 + precomputed.n2)).powi(2);
+    r0 + (1.0 - r0) * (1.0 - cos).powi(5)
+}
+
+/*
+How much of the light reaches the point, per color channel: the average over shadow rays towards
+samples on the light of what gets through everything on the way. White is fully lit, black is
+fully in shadow. Lights that give no samples, like area lights whose sample counts were set to
+zero, are tested like point lights at their center.
+*/
+pub fn is_shadowed(world: Arc<World>, light: &dyn Light, point: Tuple) -> Color {
+    let mut samples = light.samples_from(point);
+    if samples.is_empty() {
+        samples.push((light.direction_from(point), light.distance_from(point)));
+    }
+
+    let total = samples
+        .iter()
+        .fold(Color::new(0.0, 0.0, 0.0), |acc, (direction, distance)| {
+            let r = Ray::new(point, *direction);
+            acc.add(transmittance(world.clone(), light, r, *distance))
+        });
+
+    total.scalar_mul(1.0 / samples.len() as f64)
+}
+
+// Light let through along a shadow ray up to `distance`. Opaque objects block it, transparent
+// ones pass `transparency` of it tinted by their color, and objects that don't cast shadows
+// or that give off the light themselves are skipped. Each instance of an object only counts once
+// even though the ray enters and leaves it.
+fn transmittance(world: Arc<World>, light: &dyn Light, ray: Ray, distance: f64) -> Color {
+    let mut seen: Vec<&Intersection> = Vec::new();
+    let mut transmittance = Color::new(1.0, 1.0, 1.0);
+
+    let intersections = intersect_world(world, ray);
+    for i in intersections.values.iter() {
+        if i.t <= 0.0 || i.t >= distance {
+            continue;
+        }
+        if seen.iter().any(|s| same_instance(s, i)) {
+            continue;
+        }
+        seen.push(i);
+        if light.belongs_to(&i.scene_object) {
+            continue;
+        }
+
+        let (material, object_point) = {
+            let scene_object = i.scene_object.lock().unwrap();
+            (
+                scene_object.material(),
+                scene_object.world_to_object(ray.position(i.t), i),
+            )
+        };
+        if !material.casts_shadow {
+            continue;
+        }
+        if material.transparency == 0.0 {
+            return Color::new(0.0, 0.0, 0.0);
+        }
+        transmittance = transmittance.mul(
+            material
+                .color_at(object_point)
+                .scalar_mul(material.transparency),
+        );
+    }
+
+    transmittance
 }
@@ -1,16 +1,31 @@
 pub mod bin_utils;
+pub mod bounding_box;
+pub mod bvh;
 pub mod camera;
 pub mod canvas;
 pub mod colors;
+pub mod cone;
+pub mod csg;
+pub mod cube;
+pub mod cylinder;
+pub mod group;
+pub mod height_field;
 pub mod intersection;
 pub mod light;
 pub mod material;
 pub mod matrix;
+pub mod noise;
+pub mod normal_map;
+pub mod pattern;
+pub mod plane;
 pub mod ray;
 pub mod render;
 pub mod scene_object;
+pub mod shading;
 pub mod sphere;
+pub mod texture;
 pub mod transformations;
+pub mod triangle;
 pub mod tuple;
 pub mod utils;
 pub mod world;
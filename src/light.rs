@@ -1,11 +1,85 @@
+use crate::bounding_box::BoundingBox;
 use crate::colors::Color;
 use crate::material::Material;
+use crate::matrix::Matrix;
+use crate::scene_object::MutSceneObject;
+use crate::shading::{Phong, ShadingModel};
 use crate::tuple::Tuple;
+use rand::Rng;
+use std::f64::consts::PI;
+use std::fmt::Debug;
+use std::sync::Arc;
 
+pub trait Light: Debug + Send + Sync {
+    // Unit vector pointing from `point` towards the light
+    fn direction_from(&self, point: Tuple) -> Tuple;
+    // How far the light is from `point`, infinite for lights without a position
+    fn distance_from(&self, point: Tuple) -> f64;
+    // Light arriving at `point`, not accounting for anything in the way
+    fn intensity_at(&self, point: Tuple) -> Color;
+
+    // How much of the light's intensity is left after travelling to `point`
+    fn attenuation_at(&self, _point: Tuple) -> f64 {
+        1.0
+    }
+
+    // Direction and distance from `point` to every spot on the light that shadow rays should
+    // be cast towards. Lights without a surface only have the one.
+    fn samples_from(&self, point: Tuple) -> Vec<(Tuple, f64)> {
+        vec![(self.direction_from(point), self.distance_from(point))]
+    }
+
+    // Whether `object` is what gives off the light, so shadow rays towards it pass through
+    fn belongs_to(&self, _object: &MutSceneObject) -> bool {
+        false
+    }
+}
+
+/*
+How a light dims with distance:
+None: the same brightness at any distance
+Linear: 1/d
+InverseSquare: 1/d², how real point lights behave
+Coefficients: 1/(constant + linear*d + quadratic*d²), for anything in between
+ */
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Attenuation {
+    None,
+    Linear,
+    InverseSquare,
+    Coefficients {
+        constant: f64,
+        linear: f64,
+        quadratic: f64,
+    },
+}
+
+impl Attenuation {
+    // Fraction of the light left at `distance`. From `range` on the light is cut off entirely.
+    pub fn factor(&self, distance: f64, range: f64) -> f64 {
+        if distance >= range {
+            return 0.0;
+        }
+        match *self {
+            Attenuation::None => 1.0,
+            Attenuation::Linear => 1.0 / distance,
+            Attenuation::InverseSquare => 1.0 / distance.powi(2),
+            Attenuation::Coefficients {
+                constant,
+                linear,
+                quadratic,
+            } => 1.0 / (constant + linear * distance + quadratic * distance.powi(2)),
+        }
+    }
+}
+
+// Light radiating equally in all directions from a single point
 #[derive(Debug)]
 pub struct PointLight {
     pub intensity: Color,
     pub position: Tuple,
+    pub attenuation: Attenuation,
+    pub range: f64,
 }
 
 impl PointLight {
@@ -13,55 +87,448 @@ impl PointLight {
         PointLight {
             intensity,
             position,
+            attenuation: Attenuation::None,
+            range: f64::INFINITY,
+        }
+    }
+}
+
+impl Light for PointLight {
+    fn direction_from(&self, point: Tuple) -> Tuple {
+        self.position.sub(point).normalize()
+    }
+
+    fn distance_from(&self, point: Tuple) -> f64 {
+        self.position.sub(point).magnitude()
+    }
+
+    fn intensity_at(&self, _point: Tuple) -> Color {
+        self.intensity
+    }
+
+    fn attenuation_at(&self, point: Tuple) -> f64 {
+        self.attenuation
+            .factor(self.distance_from(point), self.range)
+    }
+}
+
+// Light infinitely far away shining in a single direction everywhere, like the sun
+#[derive(Debug)]
+pub struct DirectionalLight {
+    pub intensity: Color,
+    pub direction: Tuple, // the direction the light travels in
+}
+
+impl DirectionalLight {
+    pub fn new(direction: Tuple, intensity: Color) -> DirectionalLight {
+        DirectionalLight {
+            intensity,
+            direction: direction.normalize(),
+        }
+    }
+}
+
+impl Light for DirectionalLight {
+    fn direction_from(&self, _point: Tuple) -> Tuple {
+        self.direction.negate()
+    }
+
+    fn distance_from(&self, _point: Tuple) -> f64 {
+        f64::INFINITY
+    }
+
+    fn intensity_at(&self, _point: Tuple) -> Color {
+        self.intensity
+    }
+}
+
+/*
+Point light that only shines inside a cone.
+angle: angle between the direction and the edge of the cone, in radians
+falloff: width of the band at the edge of the cone, in radians, across which the light
+fades out. Zero gives a hard edge.
+ */
+#[derive(Debug)]
+pub struct SpotLight {
+    pub intensity: Color,
+    pub position: Tuple,
+    pub direction: Tuple,
+    pub angle: f64,
+    pub falloff: f64,
+    pub attenuation: Attenuation,
+    pub range: f64,
+}
+
+impl SpotLight {
+    pub fn new(
+        position: Tuple,
+        direction: Tuple,
+        angle: f64,
+        falloff: f64,
+        intensity: Color,
+    ) -> SpotLight {
+        SpotLight {
+            intensity,
+            position,
+            direction: direction.normalize(),
+            angle,
+            falloff,
+            attenuation: Attenuation::None,
+            range: f64::INFINITY,
+        }
+    }
+}
+
+impl Light for SpotLight {
+    fn direction_from(&self, point: Tuple) -> Tuple {
+        self.position.sub(point).normalize()
+    }
+
+    fn distance_from(&self, point: Tuple) -> f64 {
+        self.position.sub(point).magnitude()
+    }
+
+    fn intensity_at(&self, point: Tuple) -> Color {
+        let to_point = point.sub(self.position).normalize();
+        let theta = to_point.dot(self.direction).clamp(-1.0, 1.0).acos();
+
+        if theta >= self.angle {
+            return Color::new(0.0, 0.0, 0.0);
+        }
+        let inner = self.angle - self.falloff;
+        if theta <= inner {
+            return self.intensity;
+        }
+
+        // Smoothstep across the falloff band so the edge doesn't show a visible crease
+        let x = (self.angle - theta) / self.falloff;
+        self.intensity.scalar_mul(x * x * (3.0 - 2.0 * x))
+    }
+
+    fn attenuation_at(&self, point: Tuple) -> f64 {
+        self.attenuation
+            .factor(self.distance_from(point), self.range)
+    }
+}
+
+fn sample_towards(point: Tuple, sample: Tuple) -> (Tuple, f64) {
+    let v = sample.sub(point);
+    (v.normalize(), v.magnitude())
+}
+
+/*
+Rectangular light with one corner at `corner` and sides along `uvec` and `vvec`. Shadows are
+tested against a grid of usteps x vsteps cells on its surface, so they come out soft.
+jitter: sample a random spot in every cell instead of its center, which trades banding in
+the penumbra for noise
+ */
+#[derive(Debug)]
+pub struct AreaLight {
+    pub intensity: Color,
+    pub corner: Tuple,
+    pub uvec: Tuple,
+    pub usteps: usize,
+    pub vvec: Tuple,
+    pub vsteps: usize,
+    pub jitter: bool,
+    pub attenuation: Attenuation,
+    pub range: f64,
+}
+
+impl AreaLight {
+    pub fn new(
+        corner: Tuple,
+        uvec: Tuple,
+        usteps: usize,
+        vvec: Tuple,
+        vsteps: usize,
+        intensity: Color,
+    ) -> AreaLight {
+        assert!(
+            usteps > 0 && vsteps > 0,
+            "Area lights need at least one sample along each side"
+        );
+        AreaLight {
+            intensity,
+            corner,
+            uvec,
+            usteps,
+            vvec,
+            vsteps,
+            jitter: false,
+            attenuation: Attenuation::None,
+            range: f64::INFINITY,
+        }
+    }
+
+    pub fn center(&self) -> Tuple {
+        self.corner
+            .add(self.uvec.scalar_mul(0.5))
+            .add(self.vvec.scalar_mul(0.5))
+    }
+
+    // Point inside the cell at (u, v) of the sampling grid
+    pub fn point_on_light(&self, u: usize, v: usize) -> Tuple {
+        let (ju, jv) = if self.jitter {
+            let mut rng = rand::thread_rng();
+            (rng.gen::<f64>(), rng.gen::<f64>())
+        } else {
+            (0.5, 0.5)
+        };
+        let ustep = self.uvec.scalar_div(self.usteps as f64);
+        let vstep = self.vvec.scalar_div(self.vsteps as f64);
+        self.corner
+            .add(ustep.scalar_mul(u as f64 + ju))
+            .add(vstep.scalar_mul(v as f64 + jv))
+    }
+}
+
+impl Light for AreaLight {
+    fn direction_from(&self, point: Tuple) -> Tuple {
+        self.center().sub(point).normalize()
+    }
+
+    fn distance_from(&self, point: Tuple) -> f64 {
+        self.center().sub(point).magnitude()
+    }
+
+    fn intensity_at(&self, _point: Tuple) -> Color {
+        self.intensity
+    }
+
+    fn attenuation_at(&self, point: Tuple) -> f64 {
+        self.attenuation
+            .factor(self.distance_from(point), self.range)
+    }
+
+    fn samples_from(&self, point: Tuple) -> Vec<(Tuple, f64)> {
+        let mut res = Vec::with_capacity(self.usteps * self.vsteps);
+        for v in 0..self.vsteps {
+            for u in 0..self.usteps {
+                res.push(sample_towards(point, self.point_on_light(u, v)));
+            }
+        }
+        res
+    }
+}
+
+/*
+Spherical light. Only the disc of the sphere facing the shaded point can cast light on it,
+so that's where the shadow samples go, spread out along a sunflower spiral.
+ */
+#[derive(Debug)]
+pub struct SphereLight {
+    pub intensity: Color,
+    pub center: Tuple,
+    pub radius: f64,
+    pub samples: usize,
+    pub attenuation: Attenuation,
+    pub range: f64,
+}
+
+impl SphereLight {
+    pub fn new(center: Tuple, radius: f64, samples: usize, intensity: Color) -> SphereLight {
+        assert!(samples > 0, "Sphere lights need at least one sample");
+        SphereLight {
+            intensity,
+            center,
+            radius,
+            samples,
+            attenuation: Attenuation::None,
+            range: f64::INFINITY,
+        }
+    }
+}
+
+impl Light for SphereLight {
+    fn direction_from(&self, point: Tuple) -> Tuple {
+        self.center.sub(point).normalize()
+    }
+
+    fn distance_from(&self, point: Tuple) -> f64 {
+        self.center.sub(point).magnitude()
+    }
+
+    fn intensity_at(&self, _point: Tuple) -> Color {
+        self.intensity
+    }
+
+    fn attenuation_at(&self, point: Tuple) -> f64 {
+        self.attenuation
+            .factor(self.distance_from(point), self.range)
+    }
+
+    fn samples_from(&self, point: Tuple) -> Vec<(Tuple, f64)> {
+        // Two axes spanning the disc facing the point
+        let forward = self.direction_from(point);
+        let helper = if forward.x.abs() < 0.9 {
+            Tuple::vector(1.0, 0.0, 0.0)
+        } else {
+            Tuple::vector(0.0, 1.0, 0.0)
+        };
+        let u_axis = forward.cross(helper).normalize();
+        let v_axis = forward.cross(u_axis);
+
+        let golden_angle = PI * (3.0 - 5.0f64.sqrt());
+        (0..self.samples)
+            .map(|i| {
+                let r = self.radius * ((i as f64 + 0.5) / self.samples as f64).sqrt();
+                let theta = i as f64 * golden_angle;
+                let sample = self
+                    .center
+                    .add(u_axis.scalar_mul(r * theta.cos()))
+                    .add(v_axis.scalar_mul(r * theta.sin()));
+                sample_towards(point, sample)
+            })
+            .collect()
+    }
+}
+
+/*
+Light given off by an emissive object, so it lights up its surroundings as well as glowing
+itself. Its intensity is the emission of the object's material and its shadow samples are
+spread through the object's bounding box in world space, both taken when the light is made,
+so it needs making again if the object changes. The object has to be a single shape rather
+than a group, and still needs adding to the world to be seen. Shapes inside groups need the
+combined transform of the groups around them passed to `nested` to end up in the right place.
+Sampling the volume of the box rather than the surface of the object is an approximation:
+it's close for boxy objects, while for round or thin ones some samples end up in empty space
+beside the object and soft shadows come out a little wider than they should.
+ */
+#[derive(Debug)]
+pub struct EmissiveLight {
+    pub object: MutSceneObject,
+    pub intensity: Color,
+    pub bounds: BoundingBox,
+    pub samples: usize,
+    pub attenuation: Attenuation,
+    pub range: f64,
+}
+
+impl EmissiveLight {
+    // For a shape directly in `World.objects`
+    pub fn new(object: MutSceneObject, samples: usize) -> EmissiveLight {
+        EmissiveLight::nested(object, &Matrix::identity_matrix(4), samples)
+    }
+
+    // For a shape inside groups, whose transforms combined, outermost first, are
+    // `parent_transform`
+    pub fn nested(
+        object: MutSceneObject,
+        parent_transform: &Matrix,
+        samples: usize,
+    ) -> EmissiveLight {
+        let (intensity, bounds) = {
+            let o = object.lock().unwrap();
+            (
+                o.material().emission,
+                o.parent_space_bounds().transform(parent_transform),
+            )
+        };
+        assert!(
+            bounds.is_finite(),
+            "Emissive lights need objects with finite bounds"
+        );
+        assert!(samples > 0, "Emissive lights need at least one sample");
+        EmissiveLight {
+            object,
+            intensity,
+            bounds,
+            samples,
+            attenuation: Attenuation::None,
+            range: f64::INFINITY,
         }
     }
+
+    // The i-th sample point, from a Halton sequence so any number of them cover the box evenly
+    pub fn sample_point(&self, i: usize) -> Tuple {
+        let size = self.bounds.max.sub(self.bounds.min);
+        self.bounds.min.add(Tuple::vector(
+            size.x * radical_inverse(i + 1, 2),
+            size.y * radical_inverse(i + 1, 3),
+            size.z * radical_inverse(i + 1, 5),
+        ))
+    }
+}
+
+// Mirrors the digits of `i` in `base` around the decimal point, giving a number in 0..1
+fn radical_inverse(mut i: usize, base: usize) -> f64 {
+    let mut res = 0.0;
+    let mut digit_value = 1.0 / base as f64;
+    while i > 0 {
+        res += (i % base) as f64 * digit_value;
+        i /= base;
+        digit_value /= base as f64;
+    }
+    res
+}
+
+impl Light for EmissiveLight {
+    fn direction_from(&self, point: Tuple) -> Tuple {
+        self.bounds.centroid().sub(point).normalize()
+    }
+
+    fn distance_from(&self, point: Tuple) -> f64 {
+        self.bounds.centroid().sub(point).magnitude()
+    }
+
+    fn intensity_at(&self, _point: Tuple) -> Color {
+        self.intensity
+    }
+
+    fn attenuation_at(&self, point: Tuple) -> f64 {
+        self.attenuation
+            .factor(self.distance_from(point), self.range)
+    }
+
+    fn samples_from(&self, point: Tuple) -> Vec<(Tuple, f64)> {
+        (0..self.samples)
+            .map(|i| sample_towards(point, self.sample_point(i)))
+            .collect()
+    }
+
+    fn belongs_to(&self, object: &MutSceneObject) -> bool {
+        Arc::ptr_eq(&self.object, object)
+    }
 }
 
+// Light reflected towards the eye at `position` from a single light, using the material's
+// shading model
 pub fn lighting(
     material: &Material,
-    light: &PointLight,
+    light: &dyn Light,
     position: Tuple,
+    object_point: Tuple, // same as position, but in the space of the object being lit
     eye_vector: Tuple,
     normal_vector: Tuple,
-    in_shadow: bool,
+    light_transmittance: Color, // how much of the light reaches the point, black when fully in shadow
 ) -> Color {
     // Combine surface color with light's color/intensity
-    let effective_color = material.color.mul(light.intensity);
-
-    // Direction of the light source
-    let light_vector = light.position.sub(position).normalize();
+    let intensity = light
+        .intensity_at(position)
+        .scalar_mul(light.attenuation_at(position));
+    let color = material.color_at(object_point);
 
     // Ambient contribution
-    let ambient = effective_color.scalar_mul(material.ambient);
+    let ambient = color.mul(intensity).scalar_mul(material.ambient);
 
-    if in_shadow {
+    if light_transmittance.red() <= 0.0
+        && light_transmittance.green() <= 0.0
+        && light_transmittance.blue() <= 0.0
+    {
         return ambient;
     }
 
-    // light_dot_normal represents the cosine of the angle between the light vector and the normal
-    // vector. A negative number means the light is on the other side of the surface.
-    // Thus, only return ambient
-    let light_dot_normal = light_vector.dot(normal_vector);
-    let mut diffuse = Color::new(0.0, 0.0, 0.0); // black
-    let mut specular = Color::new(0.0, 0.0, 0.0);
-    if light_dot_normal > 0.0 {
-        diffuse = effective_color
-            .scalar_mul(material.diffuse)
-            .scalar_mul(light_dot_normal);
-
-        // reflect_dot_eye represents the cosine of the angle between the reflection vector
-        // and the eye vector. A negative number means the light reflects away from the eye,
-        // thus no specular component
-        let reflect_vector = light_vector.scalar_mul(-1.0).reflect(normal_vector);
-        let reflect_dot_eye = reflect_vector.dot(eye_vector);
-        if reflect_dot_eye > 0.0 {
-            let factor = reflect_dot_eye.powf(material.shininess);
-            specular = light
-                .intensity
-                .scalar_mul(material.specular)
-                .scalar_mul(factor);
-        }
-    }
+    let light_vector = light.direction_from(position);
+    let model: &dyn ShadingModel = material.shading_model.as_deref().unwrap_or(&Phong);
+    let direct = model.direct(
+        material,
+        color,
+        intensity,
+        light_vector,
+        eye_vector,
+        normal_vector,
+    );
 
-    ambient.add(diffuse).add(specular)
+    ambient.add(direct.mul(light_transmittance))
 }
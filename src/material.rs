@@ -1,12 +1,26 @@
 use crate::colors::Color;
+use crate::normal_map::NormalPerturbation;
+use crate::pattern::Pattern;
+use crate::shading::ShadingModel;
+use crate::tuple::Tuple;
+use std::sync::Arc;
 
-#[derive(Debug, Clone, Copy)]
+#[derive(Debug, Clone)]
 pub struct Material {
     pub color: Color,
+    pub pattern: Option<Arc<dyn Pattern>>, // used instead of `color` when set
     pub ambient: f64,
     pub diffuse: f64,
     pub specular: f64,
     pub shininess: f64,
+    pub reflective: f64, // 0 is matte, 1 is a perfect mirror
+    pub transparency: f64,
+    pub refractive_index: f64, // 1 for vacuum, about 1.5 for glass
+    pub casts_shadow: bool,
+    pub absorption: Color, // light absorbed per unit traveled inside, black absorbs nothing
+    pub normal_perturbation: Option<Arc<dyn NormalPerturbation>>, // bump or normal map
+    pub shading_model: Option<Arc<dyn ShadingModel>>, // Phong when not set
+    pub emission: Color,   // light given off regardless of lighting, black for none
 }
 
 impl PartialEq for Material {
@@ -15,7 +29,28 @@ impl PartialEq for Material {
             && self.ambient == other.ambient
             && self.diffuse == other.diffuse
             && self.specular == other.specular
-            && self.shininess == other.shininess;
+            && self.shininess == other.shininess
+            && self.reflective == other.reflective
+            && self.transparency == other.transparency
+            && self.refractive_index == other.refractive_index
+            && self.casts_shadow == other.casts_shadow
+            && self.absorption == other.absorption
+            && self.emission == other.emission
+            && match (&self.normal_perturbation, &other.normal_perturbation) {
+                (None, None) => true,
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                _ => false,
+            }
+            && match (&self.pattern, &other.pattern) {
+                (None, None) => true,
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                _ => false,
+            }
+            && match (&self.shading_model, &other.shading_model) {
+                (None, None) => true,
+                (Some(a), Some(b)) => Arc::ptr_eq(a, b),
+                _ => false,
+            };
     }
 }
 
@@ -44,10 +79,27 @@ impl Material {
     ) -> Material {
         Material {
             color,
+            pattern: None,
             ambient,
             diffuse,
             specular,
             shininess,
+            reflective: 0.0,
+            transparency: 0.0,
+            refractive_index: 1.0,
+            casts_shadow: true,
+            absorption: Color::new(0.0, 0.0, 0.0),
+            normal_perturbation: None,
+            shading_model: None,
+            emission: Color::new(0.0, 0.0, 0.0),
+        }
+    }
+
+    // Surface color at a point in the space of the object the material is on
+    pub fn color_at(&self, object_point: Tuple) -> Color {
+        match &self.pattern {
+            Some(pattern) => pattern.pattern_at(object_point),
+            None => self.color,
         }
     }
 }
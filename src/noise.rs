@@ -0,0 +1,189 @@
+use crate::tuple::Tuple;
+
+// Ken Perlin's reference permutation of 0..256
+const PERMUTATION: [u8; 256] = [
+    151, 160, 137, 91, 90, 15, 131, 13, 201, 95, 96, 53, 194, 233, 7, 225, 140, 36, 103, 30, 69,
+    142, 8, 99, 37, 240, 21, 10, 23, 190, 6, 148, 247, 120, 234, 75, 0, 26, 197, 62, 94, 252, 219,
+    203, 117, 35, 11, 32, 57, 177, 33, 88, 237, 149, 56, 87, 174, 20, 125, 136, 171, 168, 68, 175,
+    74, 165, 71, 134, 139, 48, 27, 166, 77, 146, 158, 231, 83, 111, 229, 122, 60, 211, 133, 230,
+    220, 105, 92, 41, 55, 46, 245, 40, 244, 102, 143, 54, 65, 25, 63, 161, 1, 216, 80, 73, 209, 76,
+    132, 187, 208, 89, 18, 169, 200, 196, 135, 130, 116, 188, 159, 86, 164, 100, 109, 198, 173,
+    186, 3, 64, 52, 217, 226, 250, 124, 123, 5, 202, 38, 147, 118, 126, 255, 82, 85, 212, 207, 206,
+    59, 227, 47, 16, 58, 17, 182, 189, 28, 42, 223, 183, 170, 213, 119, 248, 152, 2, 44, 154, 163,
+    70, 221, 153, 101, 155, 167, 43, 172, 9, 129, 22, 39, 253, 19, 98, 108, 110, 79, 113, 224, 232,
+    178, 185, 112, 104, 218, 246, 97, 228, 251, 34, 242, 193, 238, 210, 144, 12, 191, 179, 162,
+    241, 81, 51, 145, 235, 249, 14, 239, 107, 49, 192, 214, 31, 181, 199, 106, 157, 184, 84, 204,
+    176, 115, 121, 50, 45, 127, 4, 150, 254, 138, 236, 205, 93, 222, 114, 67, 29, 24, 72, 243, 141,
+    128, 195, 78, 66, 215, 61, 156, 180,
+];
+
+fn fade(t: f64) -> f64 {
+    t * t * t * (t * (t * 6.0 - 15.0) + 10.0)
+}
+
+fn lerp(t: f64, a: f64, b: f64) -> f64 {
+    a + t * (b - a)
+}
+
+// Dot product of the offset (x, y, z) with one of 12 gradient directions picked by `hash`
+fn grad(hash: u8, x: f64, y: f64, z: f64) -> f64 {
+    let h = hash & 15;
+    let u = if h < 8 { x } else { y };
+    let v = if h < 4 {
+        y
+    } else if h == 12 || h == 14 {
+        x
+    } else {
+        z
+    };
+    (if h & 1 == 0 { u } else { -u }) + (if h & 2 == 0 { v } else { -v })
+}
+
+// Gradient noise over a permutation table. The same seed always produces the same noise, so
+// renders are reproducible.
+#[derive(Debug, Clone)]
+pub struct Noise {
+    permutation: [u8; 256],
+}
+
+// The noise behind `perlin`, using the reference permutation
+const REFERENCE: Noise = Noise {
+    permutation: PERMUTATION,
+};
+
+// splitmix64, enough to shuffle the permutation table without pulling in an rng
+fn next_random(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9E37_79B9_7F4A_7C15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    z ^ (z >> 31)
+}
+
+impl Noise {
+    pub fn new(seed: u64) -> Noise {
+        let mut permutation = [0u8; 256];
+        for (i, p) in permutation.iter_mut().enumerate() {
+            *p = i as u8;
+        }
+
+        let mut state = seed;
+        for i in (1..256).rev() {
+            let j = (next_random(&mut state) % (i as u64 + 1)) as usize;
+            permutation.swap(i, j);
+        }
+
+        Noise { permutation }
+    }
+
+    fn p(&self, i: usize) -> usize {
+        self.permutation[i & 255] as usize
+    }
+
+    // Improved Perlin gradient noise. Smooth, roughly in -1..1 and zero at every integer point.
+    pub fn noise(&self, point: Tuple) -> f64 {
+        let (xf, yf, zf) = (point.x.floor(), point.y.floor(), point.z.floor());
+        // Wrap the cell coordinates into the permutation table so negative points work too
+        let (xi, yi, zi) = (
+            (xf as i64 & 255) as usize,
+            (yf as i64 & 255) as usize,
+            (zf as i64 & 255) as usize,
+        );
+        let (x, y, z) = (point.x - xf, point.y - yf, point.z - zf);
+        let (u, v, w) = (fade(x), fade(y), fade(z));
+
+        // Hash the eight corners of the unit cube the point is in
+        let a = self.p(xi) + yi;
+        let aa = self.p(a) + zi;
+        let ab = self.p(a + 1) + zi;
+        let b = self.p(xi + 1) + yi;
+        let ba = self.p(b) + zi;
+        let bb = self.p(b + 1) + zi;
+
+        let g = |h: usize| self.permutation[h & 255];
+        lerp(
+            w,
+            lerp(
+                v,
+                lerp(u, grad(g(aa), x, y, z), grad(g(ba), x - 1.0, y, z)),
+                lerp(
+                    u,
+                    grad(g(ab), x, y - 1.0, z),
+                    grad(g(bb), x - 1.0, y - 1.0, z),
+                ),
+            ),
+            lerp(
+                v,
+                lerp(
+                    u,
+                    grad(g(aa + 1), x, y, z - 1.0),
+                    grad(g(ba + 1), x - 1.0, y, z - 1.0),
+                ),
+                lerp(
+                    u,
+                    grad(g(ab + 1), x, y - 1.0, z - 1.0),
+                    grad(g(bb + 1), x - 1.0, y - 1.0, z - 1.0),
+                ),
+            ),
+        )
+    }
+
+    /*
+    Fractal Brownian motion: sums octaves of noise, each `lacunarity` times the frequency and
+    `gain` times the amplitude of the one before. Normalised by the total amplitude so the result
+    stays in the same range as a single octave.
+    */
+    pub fn fbm(&self, point: Tuple, octaves: usize, lacunarity: f64, gain: f64) -> f64 {
+        let mut sum = 0.0;
+        let mut total_amplitude = 0.0;
+        let mut amplitude = 1.0;
+        let mut frequency = 1.0;
+        for _ in 0..octaves {
+            sum += amplitude * self.noise(scale_point(point, frequency));
+            total_amplitude += amplitude;
+            amplitude *= gain;
+            frequency *= lacunarity;
+        }
+
+        if total_amplitude == 0.0 {
+            0.0
+        } else {
+            sum / total_amplitude
+        }
+    }
+
+    // Like fbm with the usual lacunarity 2 and gain 0.5, but summing absolute values, which gives
+    // the creased look marble veins need. In 0..1.
+    pub fn turbulence(&self, point: Tuple, octaves: usize) -> f64 {
+        let mut sum = 0.0;
+        let mut total_amplitude = 0.0;
+        let mut amplitude = 1.0;
+        let mut frequency = 1.0;
+        for _ in 0..octaves {
+            sum += amplitude * self.noise(scale_point(point, frequency)).abs();
+            total_amplitude += amplitude;
+            amplitude *= 0.5;
+            frequency *= 2.0;
+        }
+
+        if total_amplitude == 0.0 {
+            0.0
+        } else {
+            (sum / total_amplitude).min(1.0)
+        }
+    }
+}
+
+impl Default for Noise {
+    fn default() -> Self {
+        REFERENCE
+    }
+}
+
+fn scale_point(point: Tuple, factor: f64) -> Tuple {
+    Tuple::point(point.x * factor, point.y * factor, point.z * factor)
+}
+
+pub fn perlin(point: Tuple) -> f64 {
+    REFERENCE.noise(point)
+}
@@ -0,0 +1,160 @@
+use crate::noise::Noise;
+use crate::texture::{UvMapping, UvPattern};
+use crate::tuple::Tuple;
+use std::fmt::{self, Debug};
+use std::sync::Arc;
+
+// Step used to take numeric derivatives of height functions and UV mappings
+const DELTA: f64 = 0.0001;
+
+// Changes the normal a surface is shaded with, without changing its geometry
+pub trait NormalPerturbation: Debug + Send + Sync {
+    // Both the point and the normal are in the space of the object the material is on
+    fn perturb(&self, object_point: Tuple, object_normal: Tuple) -> Tuple;
+}
+
+/*
+Tilts the normal along the slope of a height function over object space, as if the surface
+was raised by `strength` times the height. Positive heights are bumps, negative ones dents.
+*/
+#[derive(Clone)]
+pub struct BumpMap {
+    pub height: Arc<dyn Fn(Tuple) -> f64 + Send + Sync>,
+    pub strength: f64,
+}
+
+/*
+Replaces the normal with one read from an image in tangent space, the usual format for normal
+maps: red points along increasing u, green along increasing v and blue straight out of the
+surface, each mapped from 0..1 to -1..1.
+*/
+#[derive(Debug, Clone)]
+pub struct NormalMap {
+    pub texture: Arc<dyn UvPattern>,
+    pub mapping: UvMapping,
+}
+
+impl BumpMap {
+    pub fn new(height: Arc<dyn Fn(Tuple) -> f64 + Send + Sync>, strength: f64) -> BumpMap {
+        BumpMap { height, strength }
+    }
+
+    // Bumps from gradient noise with `frequency` bumps per unit, for rough stone and the like
+    pub fn noise(noise: Noise, frequency: f64, strength: f64) -> BumpMap {
+        BumpMap::new(
+            Arc::new(move |p: Tuple| {
+                noise.noise(Tuple::point(
+                    p.x * frequency,
+                    p.y * frequency,
+                    p.z * frequency,
+                ))
+            }),
+            strength,
+        )
+    }
+}
+
+impl Debug for BumpMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BumpMap")
+            .field("strength", &self.strength)
+            .finish_non_exhaustive()
+    }
+}
+
+impl NormalMap {
+    pub fn new(texture: Arc<dyn UvPattern>, mapping: UvMapping) -> NormalMap {
+        NormalMap { texture, mapping }
+    }
+
+    /*
+    Directions along the surface in which u and v grow. Found by stepping along two directions
+    perpendicular to the normal and seeing how much u and v change, which works for any
+    mapping. None where the mapping is degenerate, like at the poles of a sphere.
+    */
+    fn tangent_frame(&self, point: Tuple, normal: Tuple) -> Option<(Tuple, Tuple)> {
+        let helper = if normal.x.abs() < 0.9 {
+            Tuple::vector(1.0, 0.0, 0.0)
+        } else {
+            Tuple::vector(0.0, 1.0, 0.0)
+        };
+        let e1 = normal.cross(helper).normalize();
+        let e2 = normal.cross(e1);
+
+        let (u0, v0) = self.mapping.map(point);
+        let change = |direction: Tuple| {
+            let (u, v) = self.mapping.map(point.add(direction.scalar_mul(DELTA)));
+            (unwrap_delta(u - u0), unwrap_delta(v - v0))
+        };
+        let (du1, dv1) = change(e1);
+        let (du2, dv2) = change(e2);
+
+        let det = du1 * dv2 - du2 * dv1;
+        if det.abs() < DELTA * DELTA * 1e-6 {
+            return None;
+        }
+        let tangent = e1.scalar_mul(dv2).sub(e2.scalar_mul(dv1)).scalar_div(det);
+        let bitangent = e2.scalar_mul(du1).sub(e1.scalar_mul(du2)).scalar_div(det);
+
+        // Make the frame orthonormal, keeping which way v runs
+        let tangent = tangent
+            .sub(normal.scalar_mul(tangent.dot(normal)))
+            .normalize();
+        let mut orthogonal = normal.cross(tangent);
+        if orthogonal.dot(bitangent) < 0.0 {
+            orthogonal = orthogonal.negate();
+        }
+        Some((tangent, orthogonal))
+    }
+}
+
+impl NormalPerturbation for BumpMap {
+    fn perturb(&self, object_point: Tuple, object_normal: Tuple) -> Tuple {
+        let normal = object_normal.normalize();
+        let slope = |axis: Tuple| {
+            let ahead = (self.height)(object_point.add(axis.scalar_mul(DELTA)));
+            let behind = (self.height)(object_point.sub(axis.scalar_mul(DELTA)));
+            (ahead - behind) / (2.0 * DELTA)
+        };
+        let gradient = Tuple::vector(
+            slope(Tuple::vector(1.0, 0.0, 0.0)),
+            slope(Tuple::vector(0.0, 1.0, 0.0)),
+            slope(Tuple::vector(0.0, 0.0, 1.0)),
+        );
+
+        // Only the part of the slope along the surface tilts the normal
+        let along_surface = gradient.sub(normal.scalar_mul(gradient.dot(normal)));
+        normal
+            .sub(along_surface.scalar_mul(self.strength))
+            .normalize()
+    }
+}
+
+impl NormalPerturbation for NormalMap {
+    fn perturb(&self, object_point: Tuple, object_normal: Tuple) -> Tuple {
+        let normal = object_normal.normalize();
+        let (tangent, bitangent) = match self.tangent_frame(object_point, normal) {
+            Some(frame) => frame,
+            None => return normal,
+        };
+
+        let (u, v) = self.mapping.map(object_point);
+        let texel = self.texture.uv_pattern_at(u, v);
+        tangent
+            .scalar_mul(2.0 * texel.red() - 1.0)
+            .add(bitangent.scalar_mul(2.0 * texel.green() - 1.0))
+            .add(normal.scalar_mul(2.0 * texel.blue() - 1.0))
+            .normalize()
+    }
+}
+
+// Mappings wrap around from 1 to 0, which shows up as a jump of almost a whole unit
+fn unwrap_delta(delta: f64) -> f64 {
+    if delta > 0.5 {
+        delta - 1.0
+    } else if delta < -0.5 {
+        delta + 1.0
+    } else {
+        delta
+    }
+}
@@ -0,0 +1,393 @@
+use crate::colors::Color;
+use crate::matrix::Matrix;
+use crate::noise::{perlin, Noise};
+use crate::tuple::Tuple;
+use std::f64::consts::PI;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+pub trait Pattern: Debug + Send + Sync {
+    fn transformation(&self) -> Matrix;
+    fn set_transformation(&mut self, transform: &Matrix);
+
+    // Color at a point that is already in pattern space
+    fn local_pattern_at(&self, point: Tuple) -> Color;
+
+    // Color at a point in the space of the object the pattern is on
+    fn pattern_at(&self, object_point: Tuple) -> Color {
+        let pattern_point = self
+            .transformation()
+            .invert()
+            .unwrap()
+            .tuple_mul(&object_point);
+        self.local_pattern_at(pattern_point)
+    }
+}
+
+// Alternates between two colors every unit along x
+#[derive(Debug, Clone)]
+pub struct StripePattern {
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+    pub transform: Matrix,
+}
+
+// Blends linearly from one color to the other as x goes from 0 to 1, repeating every unit
+#[derive(Debug, Clone)]
+pub struct GradientPattern {
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+    pub transform: Matrix,
+}
+
+// Concentric rings around the y axis, alternating every unit of distance in x and z
+#[derive(Debug, Clone)]
+pub struct RingPattern {
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+    pub transform: Matrix,
+}
+
+// Alternating unit cubes in all three dimensions
+#[derive(Debug, Clone)]
+pub struct CheckerPattern {
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+    pub transform: Matrix,
+}
+
+// A single color everywhere, used as the leaf of nested patterns
+#[derive(Debug, Clone)]
+pub struct SolidPattern {
+    pub color: Color,
+    pub transform: Matrix,
+}
+
+// Averages the colors of two patterns at every point
+#[derive(Debug, Clone)]
+pub struct BlendedPattern {
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+    pub transform: Matrix,
+}
+
+// Looks up another pattern at a point jittered by Perlin noise, scaled by `scale`
+#[derive(Debug, Clone)]
+pub struct PerturbedPattern {
+    pub pattern: Arc<dyn Pattern>,
+    pub scale: f64,
+    pub transform: Matrix,
+}
+
+/*
+Veins of `b` running through `a` along x. The veins are a sine wave whose phase is pushed around
+by `turbulence` times the noise turbulence at the point, summed over `octaves` octaves.
+*/
+#[derive(Debug, Clone)]
+pub struct MarblePattern {
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+    pub noise: Noise,
+    pub frequency: f64,
+    pub turbulence: f64,
+    pub octaves: usize,
+    pub transform: Matrix,
+}
+
+// Growth rings around the y axis, `rings` per unit, bent by fbm noise scaled by `turbulence`
+#[derive(Debug, Clone)]
+pub struct WoodPattern {
+    pub a: Arc<dyn Pattern>,
+    pub b: Arc<dyn Pattern>,
+    pub noise: Noise,
+    pub rings: f64,
+    pub turbulence: f64,
+    pub octaves: usize,
+    pub transform: Matrix,
+}
+
+pub fn solid(color: Color) -> Arc<dyn Pattern> {
+    Arc::new(SolidPattern::new(color))
+}
+
+impl SolidPattern {
+    pub fn new(color: Color) -> SolidPattern {
+        SolidPattern {
+            color,
+            transform: Matrix::identity_matrix(4),
+        }
+    }
+}
+
+impl BlendedPattern {
+    pub fn new(a: Arc<dyn Pattern>, b: Arc<dyn Pattern>) -> BlendedPattern {
+        BlendedPattern {
+            a,
+            b,
+            transform: Matrix::identity_matrix(4),
+        }
+    }
+}
+
+impl PerturbedPattern {
+    pub fn new(pattern: Arc<dyn Pattern>, scale: f64) -> PerturbedPattern {
+        PerturbedPattern {
+            pattern,
+            scale,
+            transform: Matrix::identity_matrix(4),
+        }
+    }
+}
+
+impl MarblePattern {
+    pub fn new(a: Color, b: Color) -> MarblePattern {
+        MarblePattern::nested(solid(a), solid(b))
+    }
+
+    pub fn nested(a: Arc<dyn Pattern>, b: Arc<dyn Pattern>) -> MarblePattern {
+        MarblePattern {
+            a,
+            b,
+            noise: Noise::default(),
+            frequency: 1.0,
+            turbulence: 5.0,
+            octaves: 6,
+            transform: Matrix::identity_matrix(4),
+        }
+    }
+}
+
+impl WoodPattern {
+    pub fn new(a: Color, b: Color) -> WoodPattern {
+        WoodPattern::nested(solid(a), solid(b))
+    }
+
+    pub fn nested(a: Arc<dyn Pattern>, b: Arc<dyn Pattern>) -> WoodPattern {
+        WoodPattern {
+            a,
+            b,
+            noise: Noise::default(),
+            rings: 4.0,
+            turbulence: 0.5,
+            octaves: 4,
+            transform: Matrix::identity_matrix(4),
+        }
+    }
+}
+
+impl StripePattern {
+    pub fn new(a: Color, b: Color) -> StripePattern {
+        StripePattern::nested(solid(a), solid(b))
+    }
+
+    pub fn nested(a: Arc<dyn Pattern>, b: Arc<dyn Pattern>) -> StripePattern {
+        StripePattern {
+            a,
+            b,
+            transform: Matrix::identity_matrix(4),
+        }
+    }
+}
+
+impl GradientPattern {
+    pub fn new(a: Color, b: Color) -> GradientPattern {
+        GradientPattern::nested(solid(a), solid(b))
+    }
+
+    pub fn nested(a: Arc<dyn Pattern>, b: Arc<dyn Pattern>) -> GradientPattern {
+        GradientPattern {
+            a,
+            b,
+            transform: Matrix::identity_matrix(4),
+        }
+    }
+}
+
+impl RingPattern {
+    pub fn new(a: Color, b: Color) -> RingPattern {
+        RingPattern::nested(solid(a), solid(b))
+    }
+
+    pub fn nested(a: Arc<dyn Pattern>, b: Arc<dyn Pattern>) -> RingPattern {
+        RingPattern {
+            a,
+            b,
+            transform: Matrix::identity_matrix(4),
+        }
+    }
+}
+
+impl CheckerPattern {
+    pub fn new(a: Color, b: Color) -> CheckerPattern {
+        CheckerPattern::nested(solid(a), solid(b))
+    }
+
+    pub fn nested(a: Arc<dyn Pattern>, b: Arc<dyn Pattern>) -> CheckerPattern {
+        CheckerPattern {
+            a,
+            b,
+            transform: Matrix::identity_matrix(4),
+        }
+    }
+}
+
+impl Pattern for StripePattern {
+    fn transformation(&self) -> Matrix {
+        self.transform.clone()
+    }
+
+    fn set_transformation(&mut self, transform: &Matrix) {
+        self.transform = transform.clone();
+    }
+
+    fn local_pattern_at(&self, point: Tuple) -> Color {
+        if point.x.floor() as i64 % 2 == 0 {
+            self.a.pattern_at(point)
+        } else {
+            self.b.pattern_at(point)
+        }
+    }
+}
+
+impl Pattern for GradientPattern {
+    fn transformation(&self) -> Matrix {
+        self.transform.clone()
+    }
+
+    fn set_transformation(&mut self, transform: &Matrix) {
+        self.transform = transform.clone();
+    }
+
+    fn local_pattern_at(&self, point: Tuple) -> Color {
+        let a = self.a.pattern_at(point);
+        let distance = self.b.pattern_at(point).sub(a);
+        let fraction = point.x - point.x.floor();
+        a.add(distance.scalar_mul(fraction))
+    }
+}
+
+impl Pattern for RingPattern {
+    fn transformation(&self) -> Matrix {
+        self.transform.clone()
+    }
+
+    fn set_transformation(&mut self, transform: &Matrix) {
+        self.transform = transform.clone();
+    }
+
+    fn local_pattern_at(&self, point: Tuple) -> Color {
+        let distance = (point.x.powi(2) + point.z.powi(2)).sqrt();
+        if distance.floor() as i64 % 2 == 0 {
+            self.a.pattern_at(point)
+        } else {
+            self.b.pattern_at(point)
+        }
+    }
+}
+
+impl Pattern for CheckerPattern {
+    fn transformation(&self) -> Matrix {
+        self.transform.clone()
+    }
+
+    fn set_transformation(&mut self, transform: &Matrix) {
+        self.transform = transform.clone();
+    }
+
+    fn local_pattern_at(&self, point: Tuple) -> Color {
+        let sum = point.x.floor() + point.y.floor() + point.z.floor();
+        if sum as i64 % 2 == 0 {
+            self.a.pattern_at(point)
+        } else {
+            self.b.pattern_at(point)
+        }
+    }
+}
+
+impl Pattern for SolidPattern {
+    fn transformation(&self) -> Matrix {
+        self.transform.clone()
+    }
+
+    fn set_transformation(&mut self, transform: &Matrix) {
+        self.transform = transform.clone();
+    }
+
+    fn local_pattern_at(&self, _point: Tuple) -> Color {
+        self.color
+    }
+}
+
+impl Pattern for BlendedPattern {
+    fn transformation(&self) -> Matrix {
+        self.transform.clone()
+    }
+
+    fn set_transformation(&mut self, transform: &Matrix) {
+        self.transform = transform.clone();
+    }
+
+    fn local_pattern_at(&self, point: Tuple) -> Color {
+        self.a
+            .pattern_at(point)
+            .add(self.b.pattern_at(point))
+            .scalar_mul(0.5)
+    }
+}
+
+impl Pattern for PerturbedPattern {
+    fn transformation(&self) -> Matrix {
+        self.transform.clone()
+    }
+
+    fn set_transformation(&mut self, transform: &Matrix) {
+        self.transform = transform.clone();
+    }
+
+    fn local_pattern_at(&self, point: Tuple) -> Color {
+        // Sample the noise at offset positions so each axis gets an independent jitter
+        let jitter = Tuple::vector(
+            perlin(point),
+            perlin(point.add(Tuple::vector(31.4, 0.0, 0.0))),
+            perlin(point.add(Tuple::vector(0.0, 0.0, 27.1))),
+        );
+        self.pattern
+            .pattern_at(point.add(jitter.scalar_mul(self.scale)))
+    }
+}
+
+impl Pattern for MarblePattern {
+    fn transformation(&self) -> Matrix {
+        self.transform.clone()
+    }
+
+    fn set_transformation(&mut self, transform: &Matrix) {
+        self.transform = transform.clone();
+    }
+
+    fn local_pattern_at(&self, point: Tuple) -> Color {
+        let phase =
+            point.x * self.frequency + self.turbulence * self.noise.turbulence(point, self.octaves);
+        let fraction = (1.0 + (phase * PI).sin()) / 2.0;
+        let a = self.a.pattern_at(point);
+        a.add(self.b.pattern_at(point).sub(a).scalar_mul(fraction))
+    }
+}
+
+impl Pattern for WoodPattern {
+    fn transformation(&self) -> Matrix {
+        self.transform.clone()
+    }
+
+    fn set_transformation(&mut self, transform: &Matrix) {
+        self.transform = transform.clone();
+    }
+
+    fn local_pattern_at(&self, point: Tuple) -> Color {
+        let distance = (point.x.powi(2) + point.z.powi(2)).sqrt() * self.rings
+            + self.turbulence * self.noise.fbm(point, self.octaves, 2.0, 0.5);
+        let fraction = distance - distance.floor();
+        let a = self.a.pattern_at(point);
+        a.add(self.b.pattern_at(point).sub(a).scalar_mul(fraction))
+    }
+}
@@ -0,0 +1,87 @@
+use std::sync::{Arc, Mutex};
+
+use crate::bounding_box::BoundingBox;
+use crate::intersection::{Intersection, Intersections};
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::scene_object::MutSceneObject;
+use crate::tuple::Tuple;
+use crate::utils::EPSILON;
+use crate::{matrix::Matrix, scene_object::SceneObject};
+use rand::Rng;
+
+// Infinite plane spanning x and z in object space
+#[derive(Debug, Clone)]
+pub struct Plane {
+    id: i32,
+    pub transform: Matrix,
+    pub material: Material,
+}
+
+impl SceneObject for Plane {
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn transformation(&self) -> Matrix {
+        self.transform.clone()
+    }
+
+    fn set_transformation(&mut self, transform: &Matrix) {
+        self.transform = transform.clone();
+    }
+
+    fn material(&self) -> Material {
+        self.material.clone()
+    }
+
+    fn set_material(&mut self, material: &Material) {
+        self.material = material.clone();
+    }
+
+    fn local_intersect(&self, ray: Ray, scene_object: MutSceneObject) -> Intersections {
+        let mut res = Intersections::new();
+
+        // A ray parallel to the plane never hits it, and a coplanar one hits it infinitely
+        // many times, which is just as invisible since the plane is infinitely thin
+        if ray.direction.y.abs() < EPSILON {
+            return res;
+        }
+
+        let t = -ray.origin.y / ray.direction.y;
+        res.push(Arc::new(Intersection::new(t, scene_object)));
+        res
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::new(
+            Tuple::point(f64::NEG_INFINITY, 0.0, f64::NEG_INFINITY),
+            Tuple::point(f64::INFINITY, 0.0, f64::INFINITY),
+        )
+    }
+
+    fn local_normal_at(&self, _point: Tuple) -> Tuple {
+        Tuple::vector(0.0, 1.0, 0.0)
+    }
+}
+
+impl Plane {
+    pub fn new() -> Plane {
+        let mut rng = rand::thread_rng();
+        Plane {
+            material: Material::default(),
+            id: rng.gen::<i32>(),
+            transform: Matrix::identity_matrix(4),
+        }
+    }
+}
+
+impl Default for Plane {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub fn plane() -> MutSceneObject {
+    Arc::new(Mutex::new(Box::new(Plane::new())))
+}
@@ -26,7 +26,7 @@ pub fn render(camera: Arc<Camera>, world: Arc<World>) -> Canvas {
         thread::spawn(move || {
             for x in 0..camera.hsize - 1 {
                 let ray = camera.ray_for_pixel(x, y);
-                let color = color_at(world.clone(), ray);
+                let color = color_at(world.clone(), ray, world.max_depth);
                 canvas.lock().unwrap().write_pixel(x, y, color);
             }
             GLOBAL_THREAD_COUNT.fetch_sub(1, Ordering::SeqCst);
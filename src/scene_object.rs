@@ -1,4 +1,8 @@
+use crate::bounding_box::BoundingBox;
+use crate::bvh::BvhStats;
+use crate::intersection::{Intersection, Intersections};
 use crate::material::Material;
+use crate::ray::Ray;
 use std::fmt::Debug;
 use std::sync::{Arc, Mutex};
 
@@ -9,9 +13,88 @@ pub trait SceneObject: Debug + Send {
     fn id(&self) -> i32;
     fn transformation(&self) -> Matrix;
     fn set_transformation(&mut self, transform: &Matrix);
-    fn normal_at(&self, point: Tuple) -> Tuple;
     fn material(&self) -> Material;
     fn set_material(&mut self, material: &Material);
+
+    // Intersects a ray that is already in object space. `scene_object` is the handle the
+    // resulting intersections should point to, usually the one wrapping `self`.
+    fn local_intersect(&self, ray: Ray, scene_object: MutSceneObject) -> Intersections;
+
+    // Same as `local_intersect`, also adding up the work done by any BVHs inside the object
+    // into `stats`
+    fn local_intersect_with_stats(
+        &self,
+        ray: Ray,
+        scene_object: MutSceneObject,
+        _stats: &mut BvhStats,
+    ) -> Intersections {
+        self.local_intersect(ray, scene_object)
+    }
+
+    // Normal at a point that is already in object space
+    fn local_normal_at(&self, point: Tuple) -> Tuple;
+
+    // Axis-aligned box around the object in its own object space
+    fn bounds(&self) -> BoundingBox;
+
+    // Box around the object in the space of whatever contains it (a group or the world)
+    fn parent_space_bounds(&self) -> BoundingBox {
+        self.bounds().transform(&self.transformation())
+    }
+
+    // Rebuilds any acceleration structures over the object's children. Only objects that
+    // contain other objects have anything to do here.
+    fn build_bvh(&mut self) {}
+
+    // Same as `local_normal_at`, for shapes that need to know more about the hit than the
+    // point, like smooth triangles interpolating vertex normals with the hit's u/v
+    fn local_normal_at_hit(&self, point: Tuple, _hit: &Intersection) -> Tuple {
+        self.local_normal_at(point)
+    }
+
+    fn normal_at(&self, world_point: Tuple) -> Tuple {
+        let inverse = self.transformation().invert().unwrap();
+        let object_point = inverse.tuple_mul(&world_point);
+        let object_normal = self.local_normal_at(object_point);
+        normal_to_world(&inverse, self.perturb_normal(object_point, object_normal))
+    }
+
+    // Shading normal after the material's bump or normal map, if it has one
+    fn perturb_normal(&self, object_point: Tuple, object_normal: Tuple) -> Tuple {
+        match &self.material().normal_perturbation {
+            Some(perturbation) => perturbation.perturb(object_point, object_normal),
+            None => object_normal,
+        }
+    }
+
+    // Transform from object space all the way to world space, through any groups the hit
+    // went through on the way down
+    fn transformation_at_hit(&self, hit: &Intersection) -> Matrix {
+        match &hit.parent_transform {
+            Some(parent) => parent.mat_mul(&self.transformation()),
+            None => self.transformation(),
+        }
+    }
+
+    fn world_to_object(&self, world_point: Tuple, hit: &Intersection) -> Tuple {
+        self.transformation_at_hit(hit)
+            .invert()
+            .unwrap()
+            .tuple_mul(&world_point)
+    }
+
+    fn normal_at_hit(&self, world_point: Tuple, hit: &Intersection) -> Tuple {
+        let inverse = self.transformation_at_hit(hit).invert().unwrap();
+        let object_point = inverse.tuple_mul(&world_point);
+        let object_normal = self.local_normal_at_hit(object_point, hit);
+        normal_to_world(&inverse, self.perturb_normal(object_point, object_normal))
+    }
+}
+
+fn normal_to_world(inverse_transform: &Matrix, object_normal: Tuple) -> Tuple {
+    let mut world_normal = inverse_transform.transpose().tuple_mul(&object_normal);
+    world_normal.w = 0.0;
+    world_normal.normalize()
 }
 
 pub type MutSceneObject = Arc<Mutex<Box<dyn SceneObject>>>;
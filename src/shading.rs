@@ -0,0 +1,204 @@
+use crate::colors::Color;
+use crate::material::Material;
+use crate::tuple::Tuple;
+use std::f64::consts::PI;
+use std::fmt::Debug;
+
+/*
+How a surface reflects the light that reaches it directly from a light source. Ambient light
+and shadows are the same for every model and are handled by `light::lighting`.
+*/
+pub trait ShadingModel: Debug + Send + Sync {
+    // Diffuse plus specular light sent towards the eye by a surface of `color`, lit with
+    // `intensity` from the direction of `light_vector`. All vectors are unit length and
+    // point away from the surface.
+    fn direct(
+        &self,
+        material: &Material,
+        color: Color,
+        intensity: Color,
+        light_vector: Tuple,
+        eye_vector: Tuple,
+        normal_vector: Tuple,
+    ) -> Color;
+}
+
+// The classic model from the book, with highlights around the mirror direction of the light.
+// Used by materials that don't pick a model.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Phong;
+
+// Phong with highlights from the half-vector between the light and the eye, which gives the
+// same highlight with about four times the shininess but doesn't cut off at grazing angles
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BlinnPhong;
+
+// Only diffuse light, for perfectly matte surfaces like chalk or unfinished wood
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Lambert;
+
+/*
+Physically based metallic/roughness model: Cook-Torrance specular with the GGX microfacet
+distribution, Smith geometry term and Schlick's Fresnel approximation, over a Lambert base.
+The material's color is the base color, and its ambient still applies, but its diffuse,
+specular and shininess are ignored.
+metallic: 0 for dielectrics like plastic, 1 for metals, whose highlights take on the base color
+and which have no diffuse light of their own
+roughness: from 0 for a polished surface with small sharp highlights to 1 for a rough one
+*/
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CookTorrance {
+    pub metallic: f64,
+    pub roughness: f64,
+}
+
+impl CookTorrance {
+    pub fn new(metallic: f64, roughness: f64) -> CookTorrance {
+        CookTorrance {
+            metallic: metallic.clamp(0.0, 1.0),
+            roughness: roughness.clamp(0.0, 1.0),
+        }
+    }
+}
+
+fn black() -> Color {
+    Color::new(0.0, 0.0, 0.0)
+}
+
+impl ShadingModel for Phong {
+    fn direct(
+        &self,
+        material: &Material,
+        color: Color,
+        intensity: Color,
+        light_vector: Tuple,
+        eye_vector: Tuple,
+        normal_vector: Tuple,
+    ) -> Color {
+        // Cosine of the angle between the light and the normal. Negative means the light is
+        // on the other side of the surface.
+        let light_dot_normal = light_vector.dot(normal_vector);
+        if light_dot_normal <= 0.0 {
+            return black();
+        }
+        let diffuse = color
+            .mul(intensity)
+            .scalar_mul(material.diffuse * light_dot_normal);
+
+        // Negative means the light reflects away from the eye, so there's no highlight
+        let reflect_vector = light_vector.negate().reflect(normal_vector);
+        let reflect_dot_eye = reflect_vector.dot(eye_vector);
+        if reflect_dot_eye <= 0.0 {
+            return diffuse;
+        }
+        let factor = reflect_dot_eye.powf(material.shininess);
+        diffuse.add(intensity.scalar_mul(material.specular * factor))
+    }
+}
+
+impl ShadingModel for BlinnPhong {
+    fn direct(
+        &self,
+        material: &Material,
+        color: Color,
+        intensity: Color,
+        light_vector: Tuple,
+        eye_vector: Tuple,
+        normal_vector: Tuple,
+    ) -> Color {
+        let light_dot_normal = light_vector.dot(normal_vector);
+        if light_dot_normal <= 0.0 {
+            return black();
+        }
+        let diffuse = color
+            .mul(intensity)
+            .scalar_mul(material.diffuse * light_dot_normal);
+
+        let halfway = light_vector.add(eye_vector).normalize();
+        let normal_dot_half = normal_vector.dot(halfway);
+        if normal_dot_half <= 0.0 {
+            return diffuse;
+        }
+        let factor = normal_dot_half.powf(material.shininess);
+        diffuse.add(intensity.scalar_mul(material.specular * factor))
+    }
+}
+
+impl ShadingModel for Lambert {
+    fn direct(
+        &self,
+        material: &Material,
+        color: Color,
+        intensity: Color,
+        light_vector: Tuple,
+        _eye_vector: Tuple,
+        normal_vector: Tuple,
+    ) -> Color {
+        let light_dot_normal = light_vector.dot(normal_vector);
+        if light_dot_normal <= 0.0 {
+            return black();
+        }
+        color
+            .mul(intensity)
+            .scalar_mul(material.diffuse * light_dot_normal)
+    }
+}
+
+/*
+The BRDF is scaled up by pi, so a light of intensity 1 lights a white matte surface facing it
+to about white, the same as with the other models, and scenes don't need their lights changed
+when switching.
+*/
+impl ShadingModel for CookTorrance {
+    fn direct(
+        &self,
+        _material: &Material,
+        color: Color,
+        intensity: Color,
+        light_vector: Tuple,
+        eye_vector: Tuple,
+        normal_vector: Tuple,
+    ) -> Color {
+        let n_dot_l = normal_vector.dot(light_vector);
+        if n_dot_l <= 0.0 {
+            return black();
+        }
+        // Keeps the eye from ending up exactly on the horizon, where the specular term blows up
+        let n_dot_v = normal_vector.dot(eye_vector).max(1e-4);
+        let halfway = light_vector.add(eye_vector).normalize();
+        let n_dot_h = normal_vector.dot(halfway).max(0.0);
+        let v_dot_h = eye_vector.dot(halfway).max(0.0);
+
+        // GGX distribution of the microfacet normals, with the usual alpha = roughness²
+        let alpha = (self.roughness * self.roughness).max(1e-3);
+        let alpha2 = alpha * alpha;
+        let d = alpha2 / (PI * (n_dot_h * n_dot_h * (alpha2 - 1.0) + 1.0).powi(2));
+
+        // Smith's shadowing and masking of the microfacets, with Schlick-GGX for each side
+        let k = (self.roughness + 1.0).powi(2) / 8.0;
+        let g1 = |n_dot_x: f64| n_dot_x / (n_dot_x * (1.0 - k) + k);
+        let g = g1(n_dot_l) * g1(n_dot_v);
+
+        // Dielectrics reflect about 4% head on, metals their own color
+        let dielectric = Color::new(0.04, 0.04, 0.04);
+        let f0 = dielectric
+            .scalar_mul(1.0 - self.metallic)
+            .add(color.scalar_mul(self.metallic));
+        let white = Color::new(1.0, 1.0, 1.0);
+        let fresnel = f0.add(white.sub(f0).scalar_mul((1.0 - v_dot_h).powi(5)));
+
+        let specular = fresnel.scalar_mul(d * g / (4.0 * n_dot_l * n_dot_v));
+        // Whatever isn't reflected at the surface is scattered back out by the diffuse base,
+        // except in metals, which absorb it
+        let diffuse = white
+            .sub(fresnel)
+            .scalar_mul(1.0 - self.metallic)
+            .mul(color)
+            .scalar_mul(1.0 / PI);
+
+        diffuse
+            .add(specular)
+            .mul(intensity)
+            .scalar_mul(PI * n_dot_l)
+    }
+}
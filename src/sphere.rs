@@ -1,6 +1,9 @@
 use std::sync::{Arc, Mutex};
 
+use crate::bounding_box::BoundingBox;
+use crate::intersection::{Intersection, Intersections};
 use crate::material::Material;
+use crate::ray::Ray;
 use crate::scene_object::MutSceneObject;
 use crate::tuple::Tuple;
 use crate::{matrix::Matrix, scene_object::SceneObject};
@@ -26,19 +29,6 @@ impl SceneObject for Sphere {
         self.transform = transform.clone();
     }
 
-    fn normal_at(&self, world_point: Tuple) -> Tuple {
-        let object_point = self.transform.invert().unwrap().tuple_mul(&world_point);
-        let object_normal = object_point.sub(Tuple::point(0.0, 0.0, 0.0));
-        let mut world_normal = self
-            .transform
-            .invert()
-            .unwrap()
-            .transpose()
-            .tuple_mul(&object_normal);
-        world_normal.w = 0.0;
-        world_normal.normalize()
-    }
-
     fn material(&self) -> Material {
         self.material.clone()
     }
@@ -46,6 +36,37 @@ impl SceneObject for Sphere {
     fn set_material(&mut self, material: &Material) {
         self.material = material.clone();
     }
+
+    fn local_intersect(&self, ray: Ray, scene_object: MutSceneObject) -> Intersections {
+        let mut res = Intersections::new();
+
+        let sphere_to_ray = ray.origin.sub(Tuple::point(0.0, 0.0, 0.0));
+
+        let a = ray.direction.dot(ray.direction);
+        let b = 2.0 * ray.direction.dot(sphere_to_ray);
+        let c = sphere_to_ray.dot(sphere_to_ray) - 1.0;
+
+        let discriminant = b.powf(2.0) - 4.0 * a * c;
+
+        if discriminant < 0.0 {
+            return res;
+        }
+
+        let t1 = (-b - discriminant.sqrt()) / (2.0 * a);
+        let t2 = (-b + discriminant.sqrt()) / (2.0 * a);
+
+        res.push(Arc::new(Intersection::new(t1, scene_object.clone())));
+        res.push(Arc::new(Intersection::new(t2, scene_object)));
+        res
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        BoundingBox::new(Tuple::point(-1.0, -1.0, -1.0), Tuple::point(1.0, 1.0, 1.0))
+    }
+
+    fn local_normal_at(&self, point: Tuple) -> Tuple {
+        point.sub(Tuple::point(0.0, 0.0, 0.0))
+    }
 }
 
 impl Sphere {
@@ -62,3 +83,11 @@ impl Sphere {
 pub fn sphere() -> MutSceneObject {
     Arc::new(Mutex::new(Box::new(Sphere::new())))
 }
+
+// Fully transparent sphere with the refractive index of glass
+pub fn glass_sphere() -> MutSceneObject {
+    let mut s = Sphere::new();
+    s.material.transparency = 1.0;
+    s.material.refractive_index = 1.5;
+    Arc::new(Mutex::new(Box::new(s)))
+}
@@ -0,0 +1,195 @@
+use crate::canvas::Canvas;
+use crate::colors::Color;
+use crate::matrix::Matrix;
+use crate::pattern::Pattern;
+use crate::tuple::Tuple;
+use std::f64::consts::PI;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+// Turns a point on the surface of an object into 2D texture coordinates, both in 0..1
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum UvMapping {
+    Spherical,   // longitude and latitude on the unit sphere
+    Planar,      // x and z, repeating every unit
+    Cylindrical, // around the y axis, with v repeating every unit of height
+    Cube,        // each face of the unit cube gets the whole texture
+}
+
+// A color source over texture coordinates instead of points in space
+pub trait UvPattern: Debug + Send + Sync {
+    fn uv_pattern_at(&self, u: f64, v: f64) -> Color;
+}
+
+// `width` by `height` squares alternating between two colors, mostly to check mappings
+#[derive(Debug, Clone)]
+pub struct UvCheckers {
+    pub width: f64,
+    pub height: f64,
+    pub a: Color,
+    pub b: Color,
+}
+
+// An image, usually read from a PNG, stretched over the whole 0..1 range. v goes up from the
+// bottom row of the image.
+#[derive(Debug)]
+pub struct UvImage {
+    pub canvas: Canvas,
+}
+
+// Pattern that maps points to texture coordinates and looks them up in a UV pattern
+#[derive(Debug, Clone)]
+pub struct TextureMapPattern {
+    pub uv_pattern: Arc<dyn UvPattern>,
+    pub mapping: UvMapping,
+    pub transform: Matrix,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CubeFace {
+    Left,
+    Right,
+    Front,
+    Back,
+    Up,
+    Down,
+}
+
+impl UvMapping {
+    pub fn map(&self, point: Tuple) -> (f64, f64) {
+        match self {
+            UvMapping::Spherical => spherical_map(point),
+            UvMapping::Planar => (point.x.rem_euclid(1.0), point.z.rem_euclid(1.0)),
+            UvMapping::Cylindrical => {
+                let theta = point.x.atan2(point.z);
+                let raw_u = theta / (2.0 * PI);
+                (1.0 - (raw_u + 0.5), point.y.rem_euclid(1.0))
+            }
+            UvMapping::Cube => cube_map(point),
+        }
+    }
+}
+
+fn spherical_map(point: Tuple) -> (f64, f64) {
+    // Azimuth around y, from -pi to pi, and the angle down from the north pole
+    let theta = point.x.atan2(point.z);
+    let radius = Tuple::vector(point.x, point.y, point.z).magnitude();
+    let phi = (point.y / radius).acos();
+
+    // Flipped so u grows counter-clockwise when looking down from above
+    let raw_u = theta / (2.0 * PI);
+    (1.0 - (raw_u + 0.5), 1.0 - phi / PI)
+}
+
+// The face of the unit cube a point is on is the one its largest coordinate points at
+pub fn face_from_point(point: Tuple) -> CubeFace {
+    let coord = point.x.abs().max(point.y.abs()).max(point.z.abs());
+    if coord == point.x {
+        CubeFace::Right
+    } else if coord == -point.x {
+        CubeFace::Left
+    } else if coord == point.y {
+        CubeFace::Up
+    } else if coord == -point.y {
+        CubeFace::Down
+    } else if coord == point.z {
+        CubeFace::Front
+    } else {
+        CubeFace::Back
+    }
+}
+
+fn cube_map(point: Tuple) -> (f64, f64) {
+    let wrap = |a: f64| a.rem_euclid(2.0) / 2.0;
+    let (x, y, z) = (point.x, point.y, point.z);
+    match face_from_point(point) {
+        CubeFace::Front => (wrap(x + 1.0), wrap(y + 1.0)),
+        CubeFace::Back => (wrap(1.0 - x), wrap(y + 1.0)),
+        CubeFace::Left => (wrap(z + 1.0), wrap(y + 1.0)),
+        CubeFace::Right => (wrap(1.0 - z), wrap(y + 1.0)),
+        CubeFace::Up => (wrap(x + 1.0), wrap(1.0 - z)),
+        CubeFace::Down => (wrap(x + 1.0), wrap(z + 1.0)),
+    }
+}
+
+impl UvCheckers {
+    pub fn new(width: f64, height: f64, a: Color, b: Color) -> UvCheckers {
+        UvCheckers {
+            width,
+            height,
+            a,
+            b,
+        }
+    }
+}
+
+impl UvImage {
+    pub fn new(canvas: Canvas) -> UvImage {
+        UvImage { canvas }
+    }
+
+    pub fn from_png_file(path: &str) -> Result<UvImage, png::DecodingError> {
+        Ok(UvImage::new(Canvas::from_png_file(path)?))
+    }
+}
+
+impl TextureMapPattern {
+    pub fn new(uv_pattern: Arc<dyn UvPattern>, mapping: UvMapping) -> TextureMapPattern {
+        TextureMapPattern {
+            uv_pattern,
+            mapping,
+            transform: Matrix::identity_matrix(4),
+        }
+    }
+}
+
+impl UvPattern for UvCheckers {
+    fn uv_pattern_at(&self, u: f64, v: f64) -> Color {
+        let u = (u * self.width).floor() as i64;
+        let v = (v * self.height).floor() as i64;
+        if (u + v).rem_euclid(2) == 0 {
+            self.a
+        } else {
+            self.b
+        }
+    }
+}
+
+impl UvPattern for UvImage {
+    // Bilinear interpolation between the four texels around the point, with texel centers at
+    // whole coordinates so the corners of the image land exactly on the corner texels
+    fn uv_pattern_at(&self, u: f64, v: f64) -> Color {
+        let max_x = self.canvas.width() - 1;
+        let max_y = self.canvas.height() - 1;
+        let x = u.clamp(0.0, 1.0) * max_x as f64;
+        let y = (1.0 - v.clamp(0.0, 1.0)) * max_y as f64;
+
+        let (x0, y0) = (x.floor() as usize, y.floor() as usize);
+        let (x1, y1) = ((x0 + 1).min(max_x), (y0 + 1).min(max_y));
+        let (fx, fy) = (x - x0 as f64, y - y0 as f64);
+
+        let texel = |x: usize, y: usize| self.canvas.pixels[y][x];
+        let top = texel(x0, y0)
+            .scalar_mul(1.0 - fx)
+            .add(texel(x1, y0).scalar_mul(fx));
+        let bottom = texel(x0, y1)
+            .scalar_mul(1.0 - fx)
+            .add(texel(x1, y1).scalar_mul(fx));
+        top.scalar_mul(1.0 - fy).add(bottom.scalar_mul(fy))
+    }
+}
+
+impl Pattern for TextureMapPattern {
+    fn transformation(&self) -> Matrix {
+        self.transform.clone()
+    }
+
+    fn set_transformation(&mut self, transform: &Matrix) {
+        self.transform = transform.clone();
+    }
+
+    fn local_pattern_at(&self, point: Tuple) -> Color {
+        let (u, v) = self.mapping.map(point);
+        self.uv_pattern.uv_pattern_at(u, v)
+    }
+}
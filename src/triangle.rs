@@ -0,0 +1,218 @@
+use std::sync::{Arc, Mutex};
+
+use crate::bounding_box::BoundingBox;
+use crate::intersection::{Intersection, Intersections};
+use crate::material::Material;
+use crate::ray::Ray;
+use crate::scene_object::MutSceneObject;
+use crate::tuple::Tuple;
+use crate::utils::EPSILON;
+use crate::{matrix::Matrix, scene_object::SceneObject};
+use rand::Rng;
+
+#[derive(Debug, Clone)]
+pub struct Triangle {
+    id: i32,
+    pub transform: Matrix,
+    pub material: Material,
+    pub p1: Tuple,
+    pub p2: Tuple,
+    pub p3: Tuple,
+    pub e1: Tuple,
+    pub e2: Tuple,
+    pub normal: Tuple,
+}
+
+// Triangle that interpolates the normals given for each vertex across its surface
+#[derive(Debug, Clone)]
+pub struct SmoothTriangle {
+    id: i32,
+    pub transform: Matrix,
+    pub material: Material,
+    pub p1: Tuple,
+    pub p2: Tuple,
+    pub p3: Tuple,
+    pub n1: Tuple,
+    pub n2: Tuple,
+    pub n3: Tuple,
+    pub e1: Tuple,
+    pub e2: Tuple,
+}
+
+// Möller–Trumbore ray/triangle intersection. Returns t along with the barycentric u and v
+// of the hit, or None if the ray misses or is parallel to the triangle.
+pub(crate) fn moller_trumbore(
+    ray: Ray,
+    p1: Tuple,
+    e1: Tuple,
+    e2: Tuple,
+) -> Option<(f64, f64, f64)> {
+    let dir_cross_e2 = ray.direction.cross(e2);
+    let det = e1.dot(dir_cross_e2);
+    if det.abs() < EPSILON {
+        return None;
+    }
+
+    let f = 1.0 / det;
+    let p1_to_origin = ray.origin.sub(p1);
+    let u = f * p1_to_origin.dot(dir_cross_e2);
+    if !(0.0..=1.0).contains(&u) {
+        return None;
+    }
+
+    let origin_cross_e1 = p1_to_origin.cross(e1);
+    let v = f * ray.direction.dot(origin_cross_e1);
+    if v < 0.0 || u + v > 1.0 {
+        return None;
+    }
+
+    let t = f * e2.dot(origin_cross_e1);
+    Some((t, u, v))
+}
+
+impl Triangle {
+    pub fn new(p1: Tuple, p2: Tuple, p3: Tuple) -> Triangle {
+        let mut rng = rand::thread_rng();
+        let e1 = p2.sub(p1);
+        let e2 = p3.sub(p1);
+        Triangle {
+            material: Material::default(),
+            id: rng.gen::<i32>(),
+            transform: Matrix::identity_matrix(4),
+            p1,
+            p2,
+            p3,
+            e1,
+            e2,
+            normal: e2.cross(e1).normalize(),
+        }
+    }
+}
+
+impl SceneObject for Triangle {
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn transformation(&self) -> Matrix {
+        self.transform.clone()
+    }
+
+    fn set_transformation(&mut self, transform: &Matrix) {
+        self.transform = transform.clone();
+    }
+
+    fn material(&self) -> Material {
+        self.material.clone()
+    }
+
+    fn set_material(&mut self, material: &Material) {
+        self.material = material.clone();
+    }
+
+    fn local_intersect(&self, ray: Ray, scene_object: MutSceneObject) -> Intersections {
+        let mut res = Intersections::new();
+        if let Some((t, u, v)) = moller_trumbore(ray, self.p1, self.e1, self.e2) {
+            res.push(Arc::new(Intersection::new_with_uv(t, scene_object, u, v)));
+        }
+        res
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        let mut res = BoundingBox::empty();
+        res.add_point(self.p1);
+        res.add_point(self.p2);
+        res.add_point(self.p3);
+        res
+    }
+
+    fn local_normal_at(&self, _point: Tuple) -> Tuple {
+        self.normal
+    }
+}
+
+impl SmoothTriangle {
+    pub fn new(p1: Tuple, p2: Tuple, p3: Tuple, n1: Tuple, n2: Tuple, n3: Tuple) -> SmoothTriangle {
+        let mut rng = rand::thread_rng();
+        SmoothTriangle {
+            material: Material::default(),
+            id: rng.gen::<i32>(),
+            transform: Matrix::identity_matrix(4),
+            p1,
+            p2,
+            p3,
+            n1,
+            n2,
+            n3,
+            e1: p2.sub(p1),
+            e2: p3.sub(p1),
+        }
+    }
+}
+
+impl SceneObject for SmoothTriangle {
+    fn id(&self) -> i32 {
+        self.id
+    }
+
+    fn transformation(&self) -> Matrix {
+        self.transform.clone()
+    }
+
+    fn set_transformation(&mut self, transform: &Matrix) {
+        self.transform = transform.clone();
+    }
+
+    fn material(&self) -> Material {
+        self.material.clone()
+    }
+
+    fn set_material(&mut self, material: &Material) {
+        self.material = material.clone();
+    }
+
+    fn local_intersect(&self, ray: Ray, scene_object: MutSceneObject) -> Intersections {
+        let mut res = Intersections::new();
+        if let Some((t, u, v)) = moller_trumbore(ray, self.p1, self.e1, self.e2) {
+            res.push(Arc::new(Intersection::new_with_uv(t, scene_object, u, v)));
+        }
+        res
+    }
+
+    fn bounds(&self) -> BoundingBox {
+        let mut res = BoundingBox::empty();
+        res.add_point(self.p1);
+        res.add_point(self.p2);
+        res.add_point(self.p3);
+        res
+    }
+
+    // Without a hit to interpolate with, fall back to the flat face normal
+    fn local_normal_at(&self, _point: Tuple) -> Tuple {
+        self.e2.cross(self.e1).normalize()
+    }
+
+    fn local_normal_at_hit(&self, _point: Tuple, hit: &Intersection) -> Tuple {
+        self.n2
+            .scalar_mul(hit.u)
+            .add(self.n3.scalar_mul(hit.v))
+            .add(self.n1.scalar_mul(1.0 - hit.u - hit.v))
+    }
+}
+
+pub fn triangle(p1: Tuple, p2: Tuple, p3: Tuple) -> MutSceneObject {
+    Arc::new(Mutex::new(Box::new(Triangle::new(p1, p2, p3))))
+}
+
+pub fn smooth_triangle(
+    p1: Tuple,
+    p2: Tuple,
+    p3: Tuple,
+    n1: Tuple,
+    n2: Tuple,
+    n3: Tuple,
+) -> MutSceneObject {
+    Arc::new(Mutex::new(Box::new(SmoothTriangle::new(
+        p1, p2, p3, n1, n2, n3,
+    ))))
+}
@@ -1,15 +1,23 @@
+use crate::bvh::Bvh;
 use crate::colors::Color;
-use crate::light::PointLight;
+use crate::light::{EmissiveLight, Light, PointLight};
 use crate::material::Material;
 use crate::scene_object::MutSceneObject;
 use crate::sphere::sphere;
 use crate::transformations::scale;
 use crate::tuple::Tuple;
 
+pub const DEFAULT_MAX_DEPTH: usize = 5;
+
 #[derive(Debug)]
 pub struct World {
     pub objects: Vec<MutSceneObject>,
-    pub light_sources: Vec<PointLight>,
+    pub light_sources: Vec<Box<dyn Light>>,
+    // Built by `build_bvh`. Has to be rebuilt after objects are moved or resized. Once objects
+    // have been added, removed or replaced it's ignored until it's rebuilt.
+    pub bvh: Option<Bvh>,
+    // How many times a ray may bounce off reflective surfaces
+    pub max_depth: usize,
 }
 
 impl World {
@@ -17,7 +25,26 @@ impl World {
         World {
             objects: Vec::new(),
             light_sources: Vec::new(),
+            bvh: None,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+
+    // Builds bounding volume hierarchies over the world and inside every group in it, so
+    // rays only get tested against objects whose bounds they pass through
+    pub fn build_bvh(&mut self) {
+        for object in self.objects.iter() {
+            object.lock().unwrap().build_bvh();
         }
+        self.bvh = Some(Bvh::new(self.objects.clone()));
+    }
+
+    // Lets an emissive object light up the rest of the world, with `samples` shadow rays cast
+    // towards points in its bounding box. The object itself still has to be added to `objects`,
+    // and shapes inside groups need their light made with `EmissiveLight::nested` instead.
+    pub fn add_emissive_light(&mut self, object: MutSceneObject, samples: usize) {
+        self.light_sources
+            .push(Box::new(EmissiveLight::new(object, samples)));
     }
 
     pub fn default_world() -> World {
@@ -40,7 +67,9 @@ impl World {
 
         World {
             objects: vec![sphere1, sphere2],
-            light_sources: vec![light],
+            light_sources: vec![Box::new(light)],
+            bvh: None,
+            max_depth: DEFAULT_MAX_DEPTH,
         }
     }
 }
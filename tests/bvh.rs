@@ -0,0 +1,349 @@
+#[cfg(test)]
+mod bvh_tests {
+    use raytracer::bounding_box::BoundingBox;
+    use raytracer::bvh::{Bvh, BvhStats};
+    use raytracer::cone::truncated_cone;
+    use raytracer::csg::{csg, CsgOperation};
+    use raytracer::cylinder::truncated_cylinder;
+    use raytracer::group::{group, Group};
+    use raytracer::intersection::{intersect_world, intersect_world_with_stats};
+    use raytracer::plane::plane;
+    use raytracer::ray::Ray;
+    use raytracer::scene_object::{MutSceneObject, SceneObject};
+    use raytracer::sphere::sphere;
+    use raytracer::transformations::{rotate_y, scale, translate};
+    use raytracer::triangle::triangle;
+    use raytracer::tuple::Tuple;
+    use raytracer::world::World;
+    use std::f64::consts::{FRAC_1_SQRT_2, PI, SQRT_2};
+    use std::sync::Arc;
+
+    fn sphere_grid(size: usize) -> Vec<MutSceneObject> {
+        let mut res = Vec::new();
+        for x in 0..size {
+            for y in 0..size {
+                let s = sphere();
+                let transform = scale(0.4, 0.4, 0.4).translate(x as f64, y as f64, 0.0);
+                s.lock().unwrap().set_transformation(&transform);
+                res.push(s);
+            }
+        }
+        res
+    }
+
+    #[test]
+    fn shape_bounds() {
+        let s = sphere();
+        assert_eq!(
+            s.lock().unwrap().bounds(),
+            BoundingBox::new(Tuple::point(-1.0, -1.0, -1.0), Tuple::point(1.0, 1.0, 1.0))
+        );
+
+        let c = truncated_cylinder(-5.0, 3.0, true);
+        assert_eq!(
+            c.lock().unwrap().bounds(),
+            BoundingBox::new(Tuple::point(-1.0, -5.0, -1.0), Tuple::point(1.0, 3.0, 1.0))
+        );
+
+        let c = truncated_cone(-5.0, 3.0, false);
+        assert_eq!(
+            c.lock().unwrap().bounds(),
+            BoundingBox::new(Tuple::point(-5.0, -5.0, -5.0), Tuple::point(5.0, 3.0, 5.0))
+        );
+
+        let t = triangle(
+            Tuple::point(-3.0, 7.0, 2.0),
+            Tuple::point(6.0, 2.0, -4.0),
+            Tuple::point(2.0, -1.0, -1.0),
+        );
+        assert_eq!(
+            t.lock().unwrap().bounds(),
+            BoundingBox::new(Tuple::point(-3.0, -1.0, -4.0), Tuple::point(6.0, 7.0, 2.0))
+        );
+
+        assert!(!plane().lock().unwrap().bounds().is_finite());
+    }
+
+    #[test]
+    fn transformed_bounds() {
+        let b = BoundingBox::new(Tuple::point(-1.0, -1.0, -1.0), Tuple::point(1.0, 1.0, 1.0));
+        let transformed = b.transform(&rotate_y(PI / 4.0).rotate_x(PI / 4.0));
+
+        let edge = 1.0 + FRAC_1_SQRT_2;
+        assert_eq!(transformed.min, Tuple::point(-SQRT_2, -edge, -edge));
+        assert_eq!(transformed.max, Tuple::point(SQRT_2, edge, edge));
+    }
+
+    #[test]
+    fn group_bounds_contain_children() {
+        let s = sphere();
+        s.lock()
+            .unwrap()
+            .set_transformation(&scale(2.0, 2.0, 2.0).translate(2.0, 5.0, -3.0));
+        let c = truncated_cylinder(-2.0, 2.0, false);
+        c.lock()
+            .unwrap()
+            .set_transformation(&scale(0.5, 1.0, 0.5).translate(-4.0, -1.0, 4.0));
+        let g = group(vec![s, c]);
+
+        assert_eq!(
+            g.lock().unwrap().bounds(),
+            BoundingBox::new(Tuple::point(-4.5, -3.0, -5.0), Tuple::point(4.0, 7.0, 4.5))
+        );
+    }
+
+    #[test]
+    fn ray_box_intersection() {
+        let b = BoundingBox::new(Tuple::point(5.0, -2.0, 0.0), Tuple::point(11.0, 4.0, 7.0));
+        let cases = [
+            (
+                Tuple::point(15.0, 1.0, 2.0),
+                Tuple::vector(-1.0, 0.0, 0.0),
+                true,
+            ),
+            (
+                Tuple::point(-5.0, -1.0, 4.0),
+                Tuple::vector(1.0, 0.0, 0.0),
+                true,
+            ),
+            (
+                Tuple::point(7.0, 6.0, 5.0),
+                Tuple::vector(0.0, -1.0, 0.0),
+                true,
+            ),
+            (
+                Tuple::point(9.0, -5.0, 6.0),
+                Tuple::vector(0.0, 1.0, 0.0),
+                true,
+            ),
+            (
+                Tuple::point(8.0, 2.0, 12.0),
+                Tuple::vector(0.0, 0.0, -1.0),
+                true,
+            ),
+            (
+                Tuple::point(6.0, 0.0, -5.0),
+                Tuple::vector(0.0, 0.0, 1.0),
+                true,
+            ),
+            (
+                Tuple::point(8.0, 1.0, 3.5),
+                Tuple::vector(0.0, 0.0, 1.0),
+                true,
+            ),
+            (
+                Tuple::point(9.0, -1.0, -8.0),
+                Tuple::vector(2.0, 4.0, 6.0),
+                false,
+            ),
+            (
+                Tuple::point(8.0, 3.0, -4.0),
+                Tuple::vector(6.0, 2.0, 4.0),
+                false,
+            ),
+            (
+                Tuple::point(9.0, -1.0, -2.0),
+                Tuple::vector(4.0, 6.0, 2.0),
+                false,
+            ),
+            (
+                Tuple::point(4.0, 0.0, 9.0),
+                Tuple::vector(0.0, 0.0, -1.0),
+                false,
+            ),
+            (
+                Tuple::point(8.0, 6.0, -1.0),
+                Tuple::vector(0.0, -1.0, 0.0),
+                false,
+            ),
+            (
+                Tuple::point(12.0, 5.0, 4.0),
+                Tuple::vector(-1.0, 0.0, 0.0),
+                false,
+            ),
+        ];
+
+        for (origin, direction, expected) in cases {
+            let r = Ray::new(origin, direction.normalize());
+            assert_eq!(b.intersects(r), expected);
+        }
+    }
+
+    #[test]
+    fn bvh_matches_linear_intersection() {
+        let mut w = World::new();
+        w.objects = sphere_grid(10);
+        w.objects.push(plane());
+        let w = Arc::new(w);
+
+        let mut with_bvh = World::new();
+        with_bvh.objects = w.objects.clone();
+        with_bvh.build_bvh();
+        let with_bvh = Arc::new(with_bvh);
+
+        for x in 0..20 {
+            for y in 0..20 {
+                let r = Ray::new(
+                    Tuple::point(-1.0 + x as f64 * 0.55, 0.3 + y as f64 * 0.5, -5.0),
+                    Tuple::vector(0.05, -0.1, 1.0).normalize(),
+                );
+                let expected = intersect_world(w.clone(), r);
+                let actual = intersect_world(with_bvh.clone(), r);
+
+                assert_eq!(actual.len(), expected.len());
+                for i in 0..expected.len() {
+                    assert_eq!(actual[i], expected[i]);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn bvh_visits_few_nodes() {
+        let bvh = Bvh::new(sphere_grid(16));
+        let r = Ray::new_flat(3.0, 7.0, -5.0, 0.0, 0.0, 1.0);
+
+        let mut stats = BvhStats::default();
+        let xs = bvh.intersect_with_stats(r, &mut stats);
+
+        assert_eq!(xs.len(), 2);
+        assert!(stats.objects_tested < 16);
+        assert!(stats.nodes_visited < 64);
+    }
+
+    #[test]
+    fn group_bvh_matches_linear_intersection() {
+        let g = group(sphere_grid(6));
+        g.lock()
+            .unwrap()
+            .set_transformation(&translate(1.0, 0.0, 2.0));
+        let r = Ray::new_flat(3.0, 2.0, -5.0, 0.0, 0.0, 1.0);
+
+        let expected = g.lock().unwrap().local_intersect(r, g.clone());
+        g.lock().unwrap().build_bvh();
+        let actual = g.lock().unwrap().local_intersect(r, g.clone());
+
+        assert_eq!(expected.len(), 2);
+        assert_eq!(actual.len(), expected.len());
+        assert_eq!(actual[0], expected[0]);
+        assert_eq!(actual[1], expected[1]);
+    }
+
+    #[test]
+    fn objects_added_after_building_bvh_are_still_hit() {
+        let mut w = World::new();
+        w.objects = sphere_grid(4);
+        w.build_bvh();
+        let extra = sphere();
+        extra
+            .lock()
+            .unwrap()
+            .set_transformation(&translate(10.0, 0.0, 0.0));
+        w.objects.push(extra);
+
+        let r = Ray::new_flat(10.0, 0.0, -5.0, 0.0, 0.0, 1.0);
+        assert_eq!(intersect_world(Arc::new(w), r).len(), 2);
+    }
+
+    #[test]
+    fn objects_replaced_after_building_bvh_are_hit_instead() {
+        let mut w = World::new();
+        w.objects = sphere_grid(1);
+        w.objects.push(sphere());
+        w.build_bvh();
+        let moved = sphere();
+        moved
+            .lock()
+            .unwrap()
+            .set_transformation(&translate(10.0, 0.0, 0.0));
+        w.objects = vec![sphere(), moved];
+        let w = Arc::new(w);
+
+        let r = Ray::new_flat(10.0, 0.0, -5.0, 0.0, 0.0, 1.0);
+        assert_eq!(intersect_world(w.clone(), r).len(), 2);
+        // The objects that were replaced are gone from the origin too, leaving just one sphere
+        let r = Ray::new_flat(0.0, 0.0, -5.0, 0.0, 0.0, 1.0);
+        assert_eq!(intersect_world(w, r).len(), 2);
+    }
+
+    #[test]
+    fn children_replaced_after_building_group_bvh_are_hit_instead() {
+        let mut g = Group::new();
+        g.add_child(sphere());
+        g.build_bvh();
+        let moved = sphere();
+        moved
+            .lock()
+            .unwrap()
+            .set_transformation(&translate(10.0, 0.0, 0.0));
+        g.children = vec![moved.clone()];
+
+        let r = Ray::new_flat(10.0, 0.0, -5.0, 0.0, 0.0, 1.0);
+        assert_eq!(g.local_intersect(r, moved.clone()).len(), 2);
+        let r = Ray::new_flat(0.0, 0.0, -5.0, 0.0, 0.0, 1.0);
+        assert_eq!(g.local_intersect(r, moved).len(), 0);
+    }
+
+    #[test]
+    fn children_pushed_after_building_group_bvh_are_still_hit() {
+        let mut g = Group::new();
+        for child in sphere_grid(4) {
+            g.add_child(child);
+        }
+        g.build_bvh();
+        let extra = sphere();
+        extra
+            .lock()
+            .unwrap()
+            .set_transformation(&translate(10.0, 0.0, 0.0));
+        g.children.push(extra.clone());
+
+        let r = Ray::new_flat(10.0, 0.0, -5.0, 0.0, 0.0, 1.0);
+        assert_eq!(g.local_intersect(r, extra).len(), 2);
+    }
+
+    #[test]
+    fn world_stats_include_group_bvhs() {
+        let mut w = World::new();
+        w.objects = vec![group(sphere_grid(16))];
+        w.build_bvh();
+        let w = Arc::new(w);
+        let r = Ray::new_flat(3.0, 7.0, -5.0, 0.0, 0.0, 1.0);
+
+        let mut stats = BvhStats::default();
+        let xs = intersect_world_with_stats(w, r, &mut stats);
+
+        assert_eq!(xs.len(), 2);
+        // The group itself and the spheres tested inside it
+        assert!(stats.objects_tested > 1 && stats.objects_tested < 17);
+        assert!(stats.nodes_visited > 1);
+    }
+
+    #[test]
+    fn world_stats_without_bvh_count_every_object() {
+        let mut w = World::new();
+        w.objects = sphere_grid(3);
+        let r = Ray::new_flat(0.0, 0.0, -5.0, 0.0, 0.0, 1.0);
+
+        let mut stats = BvhStats::default();
+        intersect_world_with_stats(Arc::new(w), r, &mut stats);
+
+        assert_eq!(stats.objects_tested, 9);
+        assert_eq!(stats.nodes_visited, 0);
+    }
+
+    #[test]
+    fn world_stats_include_bvhs_inside_csg() {
+        let mut w = World::new();
+        w.objects = vec![csg(CsgOperation::Union, group(sphere_grid(16)), sphere())];
+        w.build_bvh();
+        let r = Ray::new_flat(3.0, 7.0, -5.0, 0.0, 0.0, 1.0);
+
+        let mut stats = BvhStats::default();
+        let xs = intersect_world_with_stats(Arc::new(w), r, &mut stats);
+
+        assert_eq!(xs.len(), 2);
+        assert!(stats.objects_tested > 1);
+        assert!(stats.nodes_visited > 1);
+    }
+}
@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod cone_tests {
+    use raytracer::cone::{cone, truncated_cone};
+    use raytracer::ray::Ray;
+    use raytracer::tuple::Tuple;
+    use raytracer::utils::f64_eq;
+
+    #[test]
+    fn ray_strikes_cone() {
+        let c = cone();
+        let cases = [
+            (
+                Tuple::point(0.0, 0.0, -5.0),
+                Tuple::vector(0.0, 0.0, 1.0),
+                5.0,
+                5.0,
+            ),
+            (
+                Tuple::point(0.0, 0.0, -5.0),
+                Tuple::vector(1.0, 1.0, 1.0),
+                8.66025,
+                8.66025,
+            ),
+            (
+                Tuple::point(1.0, 1.0, -5.0),
+                Tuple::vector(-0.5, -1.0, 1.0),
+                4.55006,
+                49.44994,
+            ),
+        ];
+
+        for (origin, direction, t0, t1) in cases {
+            let r = Ray::new(origin, direction.normalize());
+            let xs = c.lock().unwrap().local_intersect(r, c.clone());
+            assert_eq!(xs.len(), 2);
+            assert!(f64_eq(xs[0].t, t0));
+            assert!(f64_eq(xs[1].t, t1));
+        }
+    }
+
+    #[test]
+    fn ray_parallel_to_one_half() {
+        let c = cone();
+        let r = Ray::new(
+            Tuple::point(0.0, 0.0, -1.0),
+            Tuple::vector(0.0, 1.0, 1.0).normalize(),
+        );
+
+        let xs = c.lock().unwrap().local_intersect(r, c.clone());
+        assert_eq!(xs.len(), 1);
+        assert!(f64_eq(xs[0].t, 0.35355));
+    }
+
+    #[test]
+    fn intersect_closed_cone_caps() {
+        let c = truncated_cone(-0.5, 0.5, true);
+        let cases = [
+            (
+                Tuple::point(0.0, 0.0, -5.0),
+                Tuple::vector(0.0, 1.0, 0.0),
+                0,
+            ),
+            (
+                Tuple::point(0.0, 0.0, -0.25),
+                Tuple::vector(0.0, 1.0, 1.0),
+                2,
+            ),
+            (
+                Tuple::point(0.0, 0.0, -0.25),
+                Tuple::vector(0.0, 1.0, 0.0),
+                4,
+            ),
+        ];
+
+        for (origin, direction, count) in cases {
+            let r = Ray::new(origin, direction.normalize());
+            let xs = c.lock().unwrap().local_intersect(r, c.clone());
+            assert_eq!(xs.len(), count);
+        }
+    }
+
+    #[test]
+    fn normal_on_cone() {
+        let c = cone();
+        let cases = [
+            (Tuple::point(0.0, 0.0, 0.0), Tuple::vector(0.0, 0.0, 0.0)),
+            (
+                Tuple::point(1.0, 1.0, 1.0),
+                Tuple::vector(1.0, -(2.0f64.sqrt()), 1.0),
+            ),
+            (Tuple::point(-1.0, -1.0, 0.0), Tuple::vector(-1.0, 1.0, 0.0)),
+        ];
+
+        for (point, expected) in cases {
+            assert_eq!(c.lock().unwrap().local_normal_at(point), expected);
+        }
+    }
+
+    #[test]
+    fn normal_on_cone_caps() {
+        let c = truncated_cone(-1.0, 1.0, true);
+
+        let top = c
+            .lock()
+            .unwrap()
+            .local_normal_at(Tuple::point(0.5, 1.0, 0.0));
+        let bottom = c
+            .lock()
+            .unwrap()
+            .local_normal_at(Tuple::point(0.0, -1.0, 0.5));
+
+        assert_eq!(top, Tuple::vector(0.0, 1.0, 0.0));
+        assert_eq!(bottom, Tuple::vector(0.0, -1.0, 0.0));
+    }
+}
@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod csg_tests {
+    use raytracer::csg::{csg, intersection_allowed, CsgOperation};
+    use raytracer::cube::cube;
+    use raytracer::intersection::intersect;
+    use raytracer::ray::Ray;
+    use raytracer::sphere::sphere;
+    use raytracer::transformations::{scale, translate};
+    use std::sync::Arc;
+
+    #[test]
+    fn rules_for_csg_operations() {
+        use CsgOperation::*;
+        let cases = [
+            (Union, true, true, true, false),
+            (Union, true, true, false, true),
+            (Union, true, false, true, false),
+            (Union, true, false, false, true),
+            (Union, false, true, true, false),
+            (Union, false, true, false, false),
+            (Union, false, false, true, true),
+            (Union, false, false, false, true),
+            (Intersection, true, true, true, true),
+            (Intersection, true, true, false, false),
+            (Intersection, true, false, true, true),
+            (Intersection, true, false, false, false),
+            (Intersection, false, true, true, true),
+            (Intersection, false, true, false, true),
+            (Intersection, false, false, true, false),
+            (Intersection, false, false, false, false),
+            (Difference, true, true, true, false),
+            (Difference, true, true, false, true),
+            (Difference, true, false, true, false),
+            (Difference, true, false, false, true),
+            (Difference, false, true, true, true),
+            (Difference, false, true, false, true),
+            (Difference, false, false, true, false),
+            (Difference, false, false, false, false),
+        ];
+
+        for (op, left_hit, inside_left, inside_right, expected) in cases {
+            assert_eq!(
+                intersection_allowed(op, left_hit, inside_left, inside_right),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn filter_intersections_by_operation() {
+        // Expected indices into the four hits on s1 (0, 2) and s2 (1, 3)
+        let cases = [
+            (CsgOperation::Union, (0, 3)),
+            (CsgOperation::Intersection, (1, 2)),
+            (CsgOperation::Difference, (0, 1)),
+        ];
+
+        for (op, (first, second)) in cases {
+            let s1 = sphere();
+            let s2 = sphere();
+            s2.lock()
+                .unwrap()
+                .set_transformation(&translate(0.0, 0.0, 0.5));
+            let c = csg(op, s1, s2);
+            let r = Ray::new_flat(0.0, 0.0, -5.0, 0.0, 0.0, 1.0);
+
+            let all = [4.0, 4.5, 6.0, 6.5];
+            let xs = c.lock().unwrap().local_intersect(r, c.clone());
+
+            assert_eq!(xs.len(), 2);
+            assert_eq!(xs[0].t, all[first]);
+            assert_eq!(xs[1].t, all[second]);
+        }
+    }
+
+    #[test]
+    fn ray_misses_csg() {
+        let c = csg(CsgOperation::Union, sphere(), cube());
+        let r = Ray::new_flat(0.0, 2.0, -5.0, 0.0, 0.0, 1.0);
+
+        let xs = c.lock().unwrap().local_intersect(r, c.clone());
+        assert_eq!(xs.len(), 0);
+    }
+
+    #[test]
+    fn ray_hits_csg() {
+        let s1 = sphere();
+        let s2 = sphere();
+        s2.lock()
+            .unwrap()
+            .set_transformation(&translate(0.0, 0.0, 0.5));
+        let c = csg(CsgOperation::Union, s1.clone(), s2.clone());
+        let r = Ray::new_flat(0.0, 0.0, -5.0, 0.0, 0.0, 1.0);
+
+        let xs = c.lock().unwrap().local_intersect(r, c.clone());
+
+        assert_eq!(xs.len(), 2);
+        assert_eq!(xs[0].t, 4.0);
+        assert!(Arc::ptr_eq(&xs[0].scene_object, &s1));
+        assert_eq!(xs[1].t, 6.5);
+        assert!(Arc::ptr_eq(&xs[1].scene_object, &s2));
+    }
+
+    #[test]
+    fn difference_carves_hole_into_cube() {
+        let hole = sphere();
+        hole.lock()
+            .unwrap()
+            .set_transformation(&scale(1.3, 1.3, 1.3));
+        let c = csg(CsgOperation::Difference, cube(), hole);
+
+        // Straight through the middle the sphere removes everything, but it doesn't reach
+        // the corners of the cube
+        let r = Ray::new_flat(0.0, 0.0, -5.0, 0.0, 0.0, 1.0);
+        assert_eq!(intersect(c.clone(), r).len(), 0);
+
+        let r = Ray::new_flat(0.95, 0.95, -5.0, 0.0, 0.0, 1.0);
+        let xs = intersect(c, r);
+        assert_eq!(xs.len(), 2);
+        assert_eq!(xs[0].t, 4.0);
+        assert_eq!(xs[1].t, 6.0);
+    }
+}
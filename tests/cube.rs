@@ -0,0 +1,91 @@
+#[cfg(test)]
+mod cube_tests {
+    use raytracer::cube::cube;
+    use raytracer::intersection::intersect;
+    use raytracer::ray::Ray;
+    use raytracer::transformations::translate;
+    use raytracer::tuple::Tuple;
+
+    #[test]
+    fn ray_intersects_cube() {
+        let c = cube();
+        let cases = [
+            // +x, -x, +y, -y, +z, -z, inside
+            ((5.0, 0.5, 0.0), (-1.0, 0.0, 0.0), 4.0, 6.0),
+            ((-5.0, 0.5, 0.0), (1.0, 0.0, 0.0), 4.0, 6.0),
+            ((0.5, 5.0, 0.0), (0.0, -1.0, 0.0), 4.0, 6.0),
+            ((0.5, -5.0, 0.0), (0.0, 1.0, 0.0), 4.0, 6.0),
+            ((0.5, 0.0, 5.0), (0.0, 0.0, -1.0), 4.0, 6.0),
+            ((0.5, 0.0, -5.0), (0.0, 0.0, 1.0), 4.0, 6.0),
+            ((0.0, 0.5, 0.0), (0.0, 0.0, 1.0), -1.0, 1.0),
+        ];
+
+        for ((ox, oy, oz), (dx, dy, dz), t1, t2) in cases {
+            let r = Ray::new_flat(ox, oy, oz, dx, dy, dz);
+            let xs = c.lock().unwrap().local_intersect(r, c.clone());
+
+            assert_eq!(xs.len(), 2);
+            assert_eq!(xs[0].t, t1);
+            assert_eq!(xs[1].t, t2);
+        }
+    }
+
+    #[test]
+    fn ray_misses_cube() {
+        let c = cube();
+        let cases = [
+            ((-2.0, 0.0, 0.0), (0.2673, 0.5345, 0.8018)),
+            ((0.0, -2.0, 0.0), (0.8018, 0.2673, 0.5345)),
+            ((0.0, 0.0, -2.0), (0.5345, 0.8018, 0.2673)),
+            ((2.0, 0.0, 2.0), (0.0, 0.0, -1.0)),
+            ((0.0, 2.0, 2.0), (0.0, -1.0, 0.0)),
+            ((2.0, 2.0, 0.0), (-1.0, 0.0, 0.0)),
+        ];
+
+        for ((ox, oy, oz), (dx, dy, dz)) in cases {
+            let r = Ray::new_flat(ox, oy, oz, dx, dy, dz);
+            let xs = c.lock().unwrap().local_intersect(r, c.clone());
+
+            assert_eq!(xs.len(), 0);
+        }
+    }
+
+    #[test]
+    fn normal_on_cube_surface() {
+        let c = cube();
+        let cases = [
+            (Tuple::point(1.0, 0.5, -0.8), Tuple::vector(1.0, 0.0, 0.0)),
+            (Tuple::point(-1.0, -0.2, 0.9), Tuple::vector(-1.0, 0.0, 0.0)),
+            (Tuple::point(-0.4, 1.0, -0.1), Tuple::vector(0.0, 1.0, 0.0)),
+            (Tuple::point(0.3, -1.0, -0.7), Tuple::vector(0.0, -1.0, 0.0)),
+            (Tuple::point(-0.6, 0.3, 1.0), Tuple::vector(0.0, 0.0, 1.0)),
+            (Tuple::point(0.4, 0.4, -1.0), Tuple::vector(0.0, 0.0, -1.0)),
+            (Tuple::point(1.0, 1.0, 1.0), Tuple::vector(1.0, 0.0, 0.0)),
+            (
+                Tuple::point(-1.0, -1.0, -1.0),
+                Tuple::vector(-1.0, 0.0, 0.0),
+            ),
+        ];
+
+        for (point, expected) in cases {
+            assert_eq!(c.lock().unwrap().local_normal_at(point), expected);
+        }
+    }
+
+    #[test]
+    fn translated_cube_intersect() {
+        let c = cube();
+        c.lock()
+            .unwrap()
+            .set_transformation(&translate(5.0, 0.0, 0.0));
+        let r = Ray::new_flat(0.0, 0.0, -5.0, 0.0, 0.0, 1.0);
+
+        assert_eq!(intersect(c.clone(), r).len(), 0);
+
+        let r = Ray::new_flat(5.0, 0.0, -5.0, 0.0, 0.0, 1.0);
+        let xs = intersect(c, r);
+        assert_eq!(xs.len(), 2);
+        assert_eq!(xs[0].t, 4.0);
+        assert_eq!(xs[1].t, 6.0);
+    }
+}
@@ -0,0 +1,153 @@
+#[cfg(test)]
+mod cylinder_tests {
+    use raytracer::cylinder::{cylinder, truncated_cylinder, Cylinder};
+    use raytracer::ray::Ray;
+    use raytracer::tuple::Tuple;
+    use raytracer::utils::f64_eq;
+
+    #[test]
+    fn ray_misses_cylinder() {
+        let c = cylinder();
+        let cases = [
+            (Tuple::point(1.0, 0.0, 0.0), Tuple::vector(0.0, 1.0, 0.0)),
+            (Tuple::point(0.0, 0.0, 0.0), Tuple::vector(0.0, 1.0, 0.0)),
+            (Tuple::point(0.0, 0.0, -5.0), Tuple::vector(1.0, 1.0, 1.0)),
+        ];
+
+        for (origin, direction) in cases {
+            let r = Ray::new(origin, direction.normalize());
+            let xs = c.lock().unwrap().local_intersect(r, c.clone());
+            assert_eq!(xs.len(), 0);
+        }
+    }
+
+    #[test]
+    fn ray_strikes_cylinder() {
+        let c = cylinder();
+        let cases = [
+            (
+                Tuple::point(1.0, 0.0, -5.0),
+                Tuple::vector(0.0, 0.0, 1.0),
+                5.0,
+                5.0,
+            ),
+            (
+                Tuple::point(0.0, 0.0, -5.0),
+                Tuple::vector(0.0, 0.0, 1.0),
+                4.0,
+                6.0,
+            ),
+            (
+                Tuple::point(0.5, 0.0, -5.0),
+                Tuple::vector(0.1, 1.0, 1.0),
+                6.80798,
+                7.08872,
+            ),
+        ];
+
+        for (origin, direction, t0, t1) in cases {
+            let r = Ray::new(origin, direction.normalize());
+            let xs = c.lock().unwrap().local_intersect(r, c.clone());
+            assert_eq!(xs.len(), 2);
+            assert!(f64_eq(xs[0].t, t0));
+            assert!(f64_eq(xs[1].t, t1));
+        }
+    }
+
+    #[test]
+    fn normal_on_cylinder() {
+        let c = cylinder();
+        let cases = [
+            (Tuple::point(1.0, 0.0, 0.0), Tuple::vector(1.0, 0.0, 0.0)),
+            (Tuple::point(0.0, 5.0, -1.0), Tuple::vector(0.0, 0.0, -1.0)),
+            (Tuple::point(0.0, -2.0, 1.0), Tuple::vector(0.0, 0.0, 1.0)),
+            (Tuple::point(-1.0, 1.0, 0.0), Tuple::vector(-1.0, 0.0, 0.0)),
+        ];
+
+        for (point, expected) in cases {
+            assert_eq!(c.lock().unwrap().local_normal_at(point), expected);
+        }
+    }
+
+    #[test]
+    fn default_cylinder_is_infinite_and_open() {
+        let c = Cylinder::new();
+        assert_eq!(c.minimum, f64::NEG_INFINITY);
+        assert_eq!(c.maximum, f64::INFINITY);
+        assert!(!c.closed);
+    }
+
+    #[test]
+    fn intersect_truncated_cylinder() {
+        let c = truncated_cylinder(1.0, 2.0, false);
+        let cases = [
+            (Tuple::point(0.0, 1.5, 0.0), Tuple::vector(0.1, 1.0, 0.0), 0),
+            (
+                Tuple::point(0.0, 3.0, -5.0),
+                Tuple::vector(0.0, 0.0, 1.0),
+                0,
+            ),
+            (
+                Tuple::point(0.0, 0.0, -5.0),
+                Tuple::vector(0.0, 0.0, 1.0),
+                0,
+            ),
+            (
+                Tuple::point(0.0, 2.0, -5.0),
+                Tuple::vector(0.0, 0.0, 1.0),
+                0,
+            ),
+            (
+                Tuple::point(0.0, 1.0, -5.0),
+                Tuple::vector(0.0, 0.0, 1.0),
+                0,
+            ),
+            (
+                Tuple::point(0.0, 1.5, -2.0),
+                Tuple::vector(0.0, 0.0, 1.0),
+                2,
+            ),
+        ];
+
+        for (origin, direction, count) in cases {
+            let r = Ray::new(origin, direction.normalize());
+            let xs = c.lock().unwrap().local_intersect(r, c.clone());
+            assert_eq!(xs.len(), count);
+        }
+    }
+
+    #[test]
+    fn intersect_closed_cylinder_caps() {
+        let c = truncated_cylinder(1.0, 2.0, true);
+        let cases = [
+            (Tuple::point(0.0, 3.0, 0.0), Tuple::vector(0.0, -1.0, 0.0)),
+            (Tuple::point(0.0, 3.0, -2.0), Tuple::vector(0.0, -1.0, 2.0)),
+            (Tuple::point(0.0, 4.0, -2.0), Tuple::vector(0.0, -1.0, 1.0)),
+            (Tuple::point(0.0, 0.0, -2.0), Tuple::vector(0.0, 1.0, 2.0)),
+            (Tuple::point(0.0, -1.0, -2.0), Tuple::vector(0.0, 1.0, 1.0)),
+        ];
+
+        for (origin, direction) in cases {
+            let r = Ray::new(origin, direction.normalize());
+            let xs = c.lock().unwrap().local_intersect(r, c.clone());
+            assert_eq!(xs.len(), 2);
+        }
+    }
+
+    #[test]
+    fn normal_on_cylinder_caps() {
+        let c = truncated_cylinder(1.0, 2.0, true);
+        let cases = [
+            (Tuple::point(0.0, 1.0, 0.0), Tuple::vector(0.0, -1.0, 0.0)),
+            (Tuple::point(0.5, 1.0, 0.0), Tuple::vector(0.0, -1.0, 0.0)),
+            (Tuple::point(0.0, 1.0, 0.5), Tuple::vector(0.0, -1.0, 0.0)),
+            (Tuple::point(0.0, 2.0, 0.0), Tuple::vector(0.0, 1.0, 0.0)),
+            (Tuple::point(0.5, 2.0, 0.0), Tuple::vector(0.0, 1.0, 0.0)),
+            (Tuple::point(0.0, 2.0, 0.5), Tuple::vector(0.0, 1.0, 0.0)),
+        ];
+
+        for (point, expected) in cases {
+            assert_eq!(c.lock().unwrap().local_normal_at(point), expected);
+        }
+    }
+}
@@ -0,0 +1,187 @@
+#[cfg(test)]
+mod emission_tests {
+    use raytracer::colors::Color;
+    use raytracer::group::group;
+    use raytracer::intersection::{color_at, is_shadowed};
+    use raytracer::light::{EmissiveLight, Light};
+    use raytracer::material::Material;
+    use raytracer::plane::plane;
+    use raytracer::ray::Ray;
+    use raytracer::scene_object::MutSceneObject;
+    use raytracer::sphere::sphere;
+    use raytracer::transformations::{scale, translate};
+    use raytracer::tuple::Tuple;
+    use raytracer::world::{World, DEFAULT_MAX_DEPTH};
+    use std::sync::Arc;
+
+    fn black() -> Color {
+        Color::new(0.0, 0.0, 0.0)
+    }
+
+    fn glowing_sphere(emission: Color) -> MutSceneObject {
+        let s = sphere();
+        let mut m = Material::default();
+        m.emission = emission;
+        s.lock().unwrap().set_material(&m);
+        s
+    }
+
+    // Floor with a glowing ball hovering 3 units above the origin
+    fn lamp_over_floor() -> (World, MutSceneObject) {
+        let lamp = glowing_sphere(Color::new(1.0, 1.0, 1.0));
+        lamp.lock()
+            .unwrap()
+            .set_transformation(&scale(0.5, 0.5, 0.5).translate(0.0, 3.0, 0.0));
+        let mut w = World::new();
+        w.objects = vec![plane(), lamp.clone()];
+        (w, lamp)
+    }
+
+    #[test]
+    fn materials_do_not_glow_by_default() {
+        assert_eq!(Material::default().emission, black());
+    }
+
+    #[test]
+    fn emissive_object_visible_without_lights() {
+        let mut w = World::new();
+        w.objects = vec![glowing_sphere(Color::new(0.2, 0.8, 1.0))];
+        let r = Ray::new_flat(0.0, 0.0, -5.0, 0.0, 0.0, 1.0);
+
+        assert_eq!(
+            color_at(Arc::new(w), r, DEFAULT_MAX_DEPTH),
+            Color::new(0.2, 0.8, 1.0)
+        );
+    }
+
+    #[test]
+    fn emission_adds_to_lit_color() {
+        let w = World::default_world();
+        let mut m = w.objects[0].lock().unwrap().material();
+        m.emission = Color::new(0.5, 0.0, 0.0);
+        w.objects[0].lock().unwrap().set_material(&m);
+        let r = Ray::new_flat(0.0, 0.0, -5.0, 0.0, 0.0, 1.0);
+
+        assert_eq!(
+            color_at(Arc::new(w), r, DEFAULT_MAX_DEPTH),
+            Color::new(0.88066, 0.47583, 0.2855)
+        );
+    }
+
+    #[test]
+    fn emissive_light_takes_emission_and_bounds_of_object() {
+        let (_, lamp) = lamp_over_floor();
+        let light = EmissiveLight::new(lamp.clone(), 16);
+
+        assert_eq!(
+            light.intensity_at(Tuple::point(0.0, 0.0, 0.0)),
+            Color::new(1.0, 1.0, 1.0)
+        );
+        assert_eq!(
+            light.direction_from(Tuple::point(0.0, 0.0, 0.0)),
+            Tuple::vector(0.0, 1.0, 0.0)
+        );
+
+        let samples = light.samples_from(Tuple::point(0.0, 0.0, 0.0));
+        assert_eq!(samples.len(), 16);
+        for i in 0..16 {
+            let p = light.sample_point(i);
+            assert!(p.x >= -0.5 && p.x <= 0.5);
+            assert!(p.y >= 2.5 && p.y <= 3.5);
+            assert!(p.z >= -0.5 && p.z <= 0.5);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn emissive_light_needs_samples() {
+        let (_, lamp) = lamp_over_floor();
+        EmissiveLight::new(lamp, 0);
+    }
+
+    #[test]
+    #[should_panic]
+    fn registering_emissive_light_needs_samples() {
+        let (mut w, lamp) = lamp_over_floor();
+        w.add_emissive_light(lamp, 0);
+    }
+
+    #[test]
+    fn emissive_light_of_shape_inside_group_is_in_world_space() {
+        let lamp = glowing_sphere(Color::new(1.0, 1.0, 1.0));
+        lamp.lock()
+            .unwrap()
+            .set_transformation(&scale(0.5, 0.5, 0.5).translate(0.0, 3.0, 0.0));
+        let g = group(vec![lamp.clone()]);
+        let group_transform = translate(5.0, 0.0, 0.0);
+        g.lock().unwrap().set_transformation(&group_transform);
+        let light = EmissiveLight::nested(lamp.clone(), &group_transform, 8);
+
+        assert_eq!(
+            light.direction_from(Tuple::point(5.0, 0.0, 0.0)),
+            Tuple::vector(0.0, 1.0, 0.0)
+        );
+        for i in 0..8 {
+            let p = light.sample_point(i);
+            assert!(p.x >= 4.5 && p.x <= 5.5);
+            assert!(p.y >= 2.5 && p.y <= 3.5);
+        }
+
+        // Its own instance in the group doesn't block it either
+        let mut w = World::new();
+        w.objects = vec![plane(), g];
+        assert_eq!(
+            is_shadowed(Arc::new(w), &light, Tuple::point(5.0, 0.0, 0.0)),
+            Color::new(1.0, 1.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn emissive_light_belongs_only_to_its_object() {
+        let (w, lamp) = lamp_over_floor();
+        let light = EmissiveLight::new(lamp.clone(), 4);
+
+        assert!(light.belongs_to(&lamp));
+        assert!(!light.belongs_to(&w.objects[0]));
+    }
+
+    #[test]
+    fn emissive_object_does_not_shadow_its_own_light() {
+        let (w, lamp) = lamp_over_floor();
+        let light = EmissiveLight::new(lamp, 8);
+
+        assert_eq!(
+            is_shadowed(Arc::new(w), &light, Tuple::point(0.0, 0.0, 0.0)),
+            Color::new(1.0, 1.0, 1.0)
+        );
+    }
+
+    #[test]
+    fn other_objects_shadow_emissive_light() {
+        let (mut w, lamp) = lamp_over_floor();
+        let blocker = sphere();
+        blocker
+            .lock()
+            .unwrap()
+            .set_transformation(&scale(2.0, 0.1, 2.0).translate(0.0, 1.5, 0.0));
+        w.objects.push(blocker);
+        let light = EmissiveLight::new(lamp, 8);
+
+        assert_eq!(
+            is_shadowed(Arc::new(w), &light, Tuple::point(0.0, 0.0, 0.0)),
+            black()
+        );
+    }
+
+    #[test]
+    fn registered_emissive_object_lights_the_floor() {
+        let r = Ray::new_flat(0.0, 1.0, -2.0, 0.0, -0.5, 1.0);
+        let (unlit, _) = lamp_over_floor();
+        let (mut lit, lamp) = lamp_over_floor();
+        lit.add_emissive_light(lamp, 8);
+
+        assert_eq!(lit.light_sources.len(), 1);
+        assert_eq!(color_at(Arc::new(unlit), r, DEFAULT_MAX_DEPTH), black());
+        assert!(color_at(Arc::new(lit), r, DEFAULT_MAX_DEPTH).red() > 0.5);
+    }
+}
@@ -0,0 +1,114 @@
+#[cfg(test)]
+mod group_tests {
+    use raytracer::group::{group, Group};
+    use raytracer::intersection::{intersect, intersect_world, prepare_computations, Intersection};
+    use raytracer::ray::Ray;
+    use raytracer::sphere::sphere;
+    use raytracer::transformations::{rotate_y, scale, translate};
+    use raytracer::tuple::Tuple;
+    use raytracer::world::World;
+    use std::f64::consts::PI;
+    use std::sync::Arc;
+
+    #[test]
+    fn empty_group_has_no_intersections() {
+        let g = group(vec![]);
+        let r = Ray::new_flat(0.0, 0.0, 0.0, 0.0, 0.0, 1.0);
+
+        let xs = g.lock().unwrap().local_intersect(r, g.clone());
+        assert_eq!(xs.len(), 0);
+    }
+
+    #[test]
+    fn add_child_to_group() {
+        let mut g = Group::new();
+        let s = sphere();
+        g.add_child(s.clone());
+
+        assert_eq!(g.children.len(), 1);
+        assert!(Arc::ptr_eq(&g.children[0], &s));
+    }
+
+    #[test]
+    fn intersect_nonempty_group() {
+        let s1 = sphere();
+        let s2 = sphere();
+        s2.lock()
+            .unwrap()
+            .set_transformation(&translate(0.0, 0.0, -3.0));
+        let s3 = sphere();
+        s3.lock()
+            .unwrap()
+            .set_transformation(&translate(5.0, 0.0, 0.0));
+        let g = group(vec![s1.clone(), s2.clone(), s3]);
+        let r = Ray::new_flat(0.0, 0.0, -5.0, 0.0, 0.0, 1.0);
+
+        let xs = g.lock().unwrap().local_intersect(r, g.clone());
+
+        assert_eq!(xs.len(), 4);
+        assert!(Arc::ptr_eq(&xs[0].scene_object, &s2));
+        assert!(Arc::ptr_eq(&xs[1].scene_object, &s2));
+        assert!(Arc::ptr_eq(&xs[2].scene_object, &s1));
+        assert!(Arc::ptr_eq(&xs[3].scene_object, &s1));
+    }
+
+    #[test]
+    fn intersect_transformed_group() {
+        let s = sphere();
+        s.lock()
+            .unwrap()
+            .set_transformation(&translate(5.0, 0.0, 0.0));
+        let g = group(vec![s]);
+        g.lock().unwrap().set_transformation(&scale(2.0, 2.0, 2.0));
+        let r = Ray::new_flat(10.0, 0.0, -10.0, 0.0, 0.0, 1.0);
+
+        let xs = intersect(g, r);
+        assert_eq!(xs.len(), 2);
+    }
+
+    #[test]
+    fn normal_on_child_of_nested_groups() {
+        let g1_transform = rotate_y(PI / 2.0);
+        let g2_transform = scale(1.0, 2.0, 3.0);
+        let s = sphere();
+        s.lock()
+            .unwrap()
+            .set_transformation(&translate(5.0, 0.0, 0.0));
+
+        let i = Intersection::new(1.0, s.clone())
+            .with_parent(&g2_transform)
+            .with_parent(&g1_transform);
+        let n = s
+            .lock()
+            .unwrap()
+            .normal_at_hit(Tuple::point(1.7321, 1.1547, -5.5774), &i);
+
+        assert_eq!(n, Tuple::vector(0.28570, 0.42854, -0.85716));
+    }
+
+    #[test]
+    fn hits_through_nested_groups_shade_in_world_space() {
+        let s = sphere();
+        s.lock()
+            .unwrap()
+            .set_transformation(&translate(5.0, 0.0, 0.0));
+        let g2 = group(vec![s.clone()]);
+        g2.lock().unwrap().set_transformation(&scale(2.0, 2.0, 2.0));
+        let g1 = group(vec![g2]);
+        g1.lock()
+            .unwrap()
+            .set_transformation(&translate(0.0, 1.0, 0.0));
+
+        let mut w = World::new();
+        w.objects.push(g1);
+        let r = Ray::new_flat(10.0, 1.0, -10.0, 0.0, 0.0, 1.0);
+
+        let xs = intersect_world(Arc::new(w), r);
+        assert_eq!(xs.len(), 2);
+        assert!(Arc::ptr_eq(&xs[0].scene_object, &s));
+
+        let comps = prepare_computations(xs.hit().unwrap(), r, &xs);
+        assert_eq!(comps.point, Tuple::point(10.0, 1.0, -2.0));
+        assert_eq!(comps.normal_vector, Tuple::vector(0.0, 0.0, -1.0));
+    }
+}
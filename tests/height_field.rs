@@ -0,0 +1,277 @@
+#[cfg(test)]
+mod height_field_tests {
+    use raytracer::canvas::Canvas;
+    use raytracer::colors::Color;
+    use raytracer::height_field::{height_field, HeightField};
+    use raytracer::intersection::intersect;
+    use raytracer::ray::Ray;
+    use raytracer::scene_object::SceneObject;
+    use raytracer::transformations::scale;
+    use raytracer::triangle::triangle;
+    use raytracer::tuple::Tuple;
+    use raytracer::utils::f64_eq;
+    use std::f64::consts::FRAC_1_SQRT_2;
+
+    fn flat(columns: usize, rows: usize, height: f64) -> Vec<Vec<f64>> {
+        vec![vec![height; columns]; rows]
+    }
+
+    // Rises from 0 at x = 0 to 1 at x = 1
+    fn ramp() -> Vec<Vec<f64>> {
+        vec![(0..5).map(|c| c as f64 / 4.0).collect(); 5]
+    }
+
+    fn bumpy() -> Vec<Vec<f64>> {
+        (0..8)
+            .map(|r| {
+                (0..8)
+                    .map(|c| 0.3 + 0.2 * (c as f64 * 1.3).sin() * (r as f64 * 0.7).cos())
+                    .collect()
+            })
+            .collect()
+    }
+
+    fn ts(h: &HeightField, ray: Ray) -> Vec<f64> {
+        let hf = height_field(vec![vec![0.0; 2]; 2]);
+        let mut xs = h.local_intersect(ray, hf);
+        xs.sort();
+        xs.values.iter().map(|i| i.t).collect()
+    }
+
+    #[test]
+    fn ray_from_above_hits_flat_field() {
+        let h = HeightField::new(flat(3, 3, 0.5));
+        let r = Ray::new_flat(0.3, 2.0, 0.7, 0.0, -1.0, 0.0);
+
+        assert_eq!(ts(&h, r), vec![1.5]);
+        assert_eq!(
+            h.local_normal_at(Tuple::point(0.3, 0.5, 0.7)),
+            Tuple::vector(0.0, 1.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn ray_outside_the_field_misses() {
+        let h = HeightField::new(flat(3, 3, 0.5));
+
+        assert!(ts(&h, Ray::new_flat(1.5, 2.0, 0.5, 0.0, -1.0, 0.0)).is_empty());
+        assert!(ts(&h, Ray::new_flat(0.5, 2.0, 0.5, 1.0, 0.0, 0.0)).is_empty());
+    }
+
+    #[test]
+    fn ray_hits_sloped_field() {
+        let h = HeightField::new(ramp());
+        let r = Ray::new_flat(0.6, 3.0, 0.2, 0.0, -1.0, 0.0);
+
+        let hits = ts(&h, r);
+        assert_eq!(hits.len(), 1);
+        assert!(f64_eq(hits[0], 2.4));
+        assert_eq!(
+            h.local_normal_at(Tuple::point(0.6, 0.6, 0.2)),
+            Tuple::vector(-FRAC_1_SQRT_2, FRAC_1_SQRT_2, 0.0)
+        );
+    }
+
+    #[test]
+    fn horizontal_ray_crosses_ridge_twice() {
+        // A ridge along z in the middle of the field
+        let h = HeightField::new(vec![vec![0.0, 1.0, 0.0]; 3]);
+        let r = Ray::new_flat(-1.0, 0.25, 0.5, 1.0, 0.0, 0.0);
+
+        let hits = ts(&h, r);
+        assert_eq!(hits.len(), 2);
+        assert!(f64_eq(hits[0], 1.125));
+        assert!(f64_eq(hits[1], 1.875));
+    }
+
+    #[test]
+    fn grid_traversal_finds_same_hits_as_every_triangle() {
+        let heights = bumpy();
+        let h = HeightField::new(heights.clone());
+        let cells = (heights.len() - 1) as f64;
+        let vertex =
+            |c: usize, r: usize| Tuple::point(c as f64 / cells, heights[r][c], r as f64 / cells);
+
+        for (origin, direction) in [
+            (Tuple::point(-0.2, 0.8, -0.1), Tuple::vector(1.0, -0.6, 0.4)),
+            (Tuple::point(0.3, 0.8, -0.1), Tuple::vector(0.2, -0.6, 1.0)),
+            (Tuple::point(-0.2, 0.8, -0.1), Tuple::vector(0.7, -1.0, 0.6)),
+            (Tuple::point(1.5, 0.5, 0.8), Tuple::vector(-1.0, -0.3, -0.2)),
+        ] {
+            let r = Ray::new(origin, direction.normalize());
+
+            let mut expected = vec![];
+            for row in 0..heights.len() - 1 {
+                for column in 0..heights.len() - 1 {
+                    for (p1, p2, p3) in [
+                        (
+                            vertex(column, row),
+                            vertex(column + 1, row),
+                            vertex(column, row + 1),
+                        ),
+                        (
+                            vertex(column + 1, row + 1),
+                            vertex(column, row + 1),
+                            vertex(column + 1, row),
+                        ),
+                    ] {
+                        let t = triangle(p1, p2, p3);
+                        let xs = t.lock().unwrap().local_intersect(r, t.clone());
+                        expected.extend(xs.values.iter().map(|i| i.t));
+                    }
+                }
+            }
+            expected.sort_by(|a, b| a.total_cmp(b));
+
+            let actual = ts(&h, r);
+            assert!(!actual.is_empty());
+            assert_eq!(actual.len(), expected.len());
+            for (a, e) in actual.iter().zip(expected.iter()) {
+                assert!(f64_eq(*a, *e));
+            }
+        }
+    }
+
+    #[test]
+    fn bounds_cover_the_unit_square_and_all_heights() {
+        let h = HeightField::new(bumpy());
+        let b = h.bounds();
+
+        assert_eq!(b.min.x, 0.0);
+        assert_eq!(b.max.z, 1.0);
+        assert!(b.min.y >= 0.1 && b.max.y <= 0.5);
+    }
+
+    #[test]
+    fn heights_from_image_brightness() {
+        let mut canvas = Canvas::new(3, 2);
+        canvas.write_pixel(1, 0, Color::new(1.0, 1.0, 1.0));
+        canvas.write_pixel(2, 1, Color::new(0.2, 0.2, 0.2));
+        let h = HeightField::from_canvas(&canvas, 4.0);
+
+        assert_eq!(h.columns(), 3);
+        assert_eq!(h.rows(), 2);
+        assert_eq!(h.height_at(0, 0), 0.0);
+        assert_eq!(h.height_at(1, 0), 4.0);
+        assert!(f64_eq(h.height_at(2, 1), 0.8));
+    }
+
+    #[test]
+    fn heights_from_png() {
+        let mut canvas = Canvas::new(2, 2);
+        canvas.write_pixel(0, 1, Color::new(1.0, 1.0, 1.0));
+        let path = std::env::temp_dir().join("raytracer_height_field_test.png");
+        let path = path.to_str().unwrap();
+        canvas.to_png_file(path);
+
+        let h = HeightField::from_png_file(path, 2.0).unwrap();
+        assert_eq!(h.height_at(0, 1), 2.0);
+        assert_eq!(h.height_at(1, 1), 0.0);
+        assert!(HeightField::from_png_file("/nonexistent/terrain.png", 1.0).is_err());
+    }
+
+    #[test]
+    fn transformed_field_in_world_space() {
+        let hf = height_field(flat(4, 4, 0.5));
+        hf.lock()
+            .unwrap()
+            .set_transformation(&scale(10.0, 2.0, 10.0));
+        let r = Ray::new_flat(7.0, 5.0, 3.0, 0.0, -1.0, 0.0);
+
+        let xs = intersect(hf, r);
+        assert_eq!(xs.len(), 1);
+        assert!(f64_eq(xs[0].t, 4.0));
+    }
+
+    // Writes a grayscale PNG with the given bit depth and raw rows of sample data
+    fn write_grayscale_png(
+        name: &str,
+        width: u32,
+        height: u32,
+        color_type: png::ColorType,
+        bit_depth: png::BitDepth,
+        data: &[u8],
+    ) -> String {
+        let path = std::env::temp_dir().join(name);
+        let file = std::fs::File::create(&path).unwrap();
+        let mut encoder = png::Encoder::new(std::io::BufWriter::new(file), width, height);
+        encoder.set_color(color_type);
+        encoder.set_depth(bit_depth);
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(data).unwrap();
+        writer.finish().unwrap();
+        path.to_str().unwrap().to_string()
+    }
+
+    #[test]
+    fn heights_from_8_bit_grayscale_png() {
+        let path = write_grayscale_png(
+            "raytracer_height_field_gray8.png",
+            3,
+            2,
+            png::ColorType::Grayscale,
+            png::BitDepth::Eight,
+            &[0, 255, 51, 102, 0, 204],
+        );
+
+        let h = HeightField::from_png_file(&path, 2.0).unwrap();
+        assert_eq!((h.columns(), h.rows()), (3, 2));
+        assert_eq!(h.height_at(0, 0), 0.0);
+        assert_eq!(h.height_at(1, 0), 2.0);
+        assert!(f64_eq(h.height_at(2, 0), 0.4));
+        assert!(f64_eq(h.height_at(0, 1), 0.8));
+        assert!(f64_eq(h.height_at(2, 1), 1.6));
+    }
+
+    #[test]
+    fn heights_from_16_bit_grayscale_png() {
+        // Big-endian samples, of which only the high byte is kept
+        let path = write_grayscale_png(
+            "raytracer_height_field_gray16.png",
+            2,
+            2,
+            png::ColorType::Grayscale,
+            png::BitDepth::Sixteen,
+            &[0xff, 0xff, 0x00, 0x00, 0x33, 0x00, 0x66, 0xff],
+        );
+
+        let h = HeightField::from_png_file(&path, 1.0).unwrap();
+        assert_eq!(h.height_at(0, 0), 1.0);
+        assert_eq!(h.height_at(1, 0), 0.0);
+        assert!(f64_eq(h.height_at(0, 1), 0.2));
+        assert!(f64_eq(h.height_at(1, 1), 0.4));
+    }
+
+    #[test]
+    fn heights_from_low_bit_depth_and_alpha_grayscale_pngs() {
+        // Two 4-bit samples per byte, expanded to the full 0..255 range
+        let path = write_grayscale_png(
+            "raytracer_height_field_gray4.png",
+            2,
+            2,
+            png::ColorType::Grayscale,
+            png::BitDepth::Four,
+            &[0xf0, 0x5a],
+        );
+        let h = HeightField::from_png_file(&path, 1.0).unwrap();
+        assert_eq!(h.height_at(0, 0), 1.0);
+        assert_eq!(h.height_at(1, 0), 0.0);
+        assert!(f64_eq(h.height_at(0, 1), 1.0 / 3.0));
+        assert!(f64_eq(h.height_at(1, 1), 2.0 / 3.0));
+
+        // Alpha is ignored
+        let path = write_grayscale_png(
+            "raytracer_height_field_gray_alpha.png",
+            2,
+            2,
+            png::ColorType::GrayscaleAlpha,
+            png::BitDepth::Eight,
+            &[255, 0, 0, 255, 51, 128, 102, 255],
+        );
+        let h = HeightField::from_png_file(&path, 1.0).unwrap();
+        assert_eq!(h.height_at(0, 0), 1.0);
+        assert_eq!(h.height_at(1, 0), 0.0);
+        assert!(f64_eq(h.height_at(0, 1), 0.2));
+        assert!(f64_eq(h.height_at(1, 1), 0.4));
+    }
+}
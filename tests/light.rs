@@ -1,10 +1,16 @@
 #[cfg(test)]
 mod light_tests {
     use raytracer::colors::Color;
-    use raytracer::light::{lighting, PointLight};
+    use raytracer::light::{lighting, Attenuation, DirectionalLight, Light, PointLight, SpotLight};
     use raytracer::material::Material;
     use raytracer::sphere::sphere;
     use raytracer::tuple::Tuple;
+    use std::f64::consts::PI;
+
+    // Light transmittance that lets through the same fraction of every channel
+    fn visible(fraction: f64) -> Color {
+        Color::new(fraction, fraction, fraction)
+    }
 
     #[test]
     fn test_point_light() {
@@ -44,7 +50,7 @@ mod light_tests {
         let light = PointLight::new(Tuple::point(0.0, 0.0, -10.0), Color::new(1.0, 1.0, 1.0));
 
         let expected = Color::new(1.9, 1.9, 1.9);
-        let actual = lighting(&m, &light, position, eye, normal, false);
+        let actual = lighting(&m, &light, position, position, eye, normal, visible(1.0));
 
         assert_eq!(expected, actual);
     }
@@ -59,7 +65,7 @@ mod light_tests {
         let light = PointLight::new(Tuple::point(0.0, 0.0, -10.0), Color::new(1.0, 1.0, 1.0));
 
         let expected = Color::new(1.0, 1.0, 1.0);
-        let actual = lighting(&m, &light, position, eye, normal, false);
+        let actual = lighting(&m, &light, position, position, eye, normal, visible(1.0));
 
         assert_eq!(expected, actual);
     }
@@ -73,7 +79,7 @@ mod light_tests {
         let light = PointLight::new(Tuple::point(0.0, 10.0, -10.0), Color::new(1.0, 1.0, 1.0));
 
         let expected = Color::new(0.7364, 0.7364, 0.7364);
-        let actual = lighting(&m, &light, position, eye, normal, false);
+        let actual = lighting(&m, &light, position, position, eye, normal, visible(1.0));
 
         assert_eq!(expected, actual);
     }
@@ -88,7 +94,7 @@ mod light_tests {
         let light = PointLight::new(Tuple::point(0.0, 10.0, -10.0), Color::new(1.0, 1.0, 1.0));
 
         let expected = Color::new(1.6364, 1.6364, 1.6364);
-        let actual = lighting(&m, &light, position, eye, normal, false);
+        let actual = lighting(&m, &light, position, position, eye, normal, visible(1.0));
 
         assert_eq!(expected, actual);
     }
@@ -102,7 +108,7 @@ mod light_tests {
         let light = PointLight::new(Tuple::point(0.0, 0.0, 10.0), Color::new(1.0, 1.0, 1.0));
 
         let expected = Color::new(0.1, 0.1, 0.1);
-        let actual = lighting(&m, &light, position, eye, normal, false);
+        let actual = lighting(&m, &light, position, position, eye, normal, visible(1.0));
 
         assert_eq!(expected, actual);
     }
@@ -117,8 +123,166 @@ mod light_tests {
         let position = Tuple::point(0.0, 0.0, 0.0);
 
         let expected = Color::new(0.1, 0.1, 0.1);
-        let actual = lighting(&m, &light, position, eye, normal, true);
+        let actual = lighting(&m, &light, position, position, eye, normal, visible(0.0));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_point_light_direction_and_distance() {
+        let light = PointLight::new(Tuple::point(0.0, 10.0, 0.0), Color::new(1.0, 1.0, 1.0));
+        let p = Tuple::point(0.0, 2.0, 0.0);
+
+        assert_eq!(light.direction_from(p), Tuple::vector(0.0, 1.0, 0.0));
+        assert_eq!(light.distance_from(p), 8.0);
+    }
+
+    #[test]
+    fn test_directional_light() {
+        let intensity = Color::new(1.0, 1.0, 1.0);
+        let light = DirectionalLight::new(Tuple::vector(0.0, -2.0, 0.0), intensity);
+        let p = Tuple::point(3.0, -100.0, 7.0);
+
+        assert_eq!(light.direction_from(p), Tuple::vector(0.0, 1.0, 0.0));
+        assert_eq!(light.distance_from(p), f64::INFINITY);
+        assert_eq!(light.intensity_at(p), intensity);
+    }
+
+    #[test]
+    fn test_lighting_directional_light_facing_surface() {
+        let m = Material::default();
+        let position = Tuple::point(0.0, 0.0, 0.0);
+        let eye = Tuple::vector(0.0, 0.0, -1.0);
+        let normal = Tuple::vector(0.0, 0.0, -1.0);
+        let light = DirectionalLight::new(Tuple::vector(0.0, 0.0, 1.0), Color::new(1.0, 1.0, 1.0));
+
+        let expected = Color::new(1.9, 1.9, 1.9);
+        let actual = lighting(&m, &light, position, position, eye, normal, visible(1.0));
 
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    fn test_spot_light_cone() {
+        let intensity = Color::new(1.0, 1.0, 1.0);
+        let light = SpotLight::new(
+            Tuple::point(0.0, 10.0, 0.0),
+            Tuple::vector(0.0, -1.0, 0.0),
+            PI / 4.0,
+            PI / 8.0,
+            intensity,
+        );
+
+        // Straight below, well inside the cone
+        assert_eq!(light.intensity_at(Tuple::point(0.0, 0.0, 0.0)), intensity);
+        // 45 degrees out, on the very edge of the cone
+        assert_eq!(
+            light.intensity_at(Tuple::point(10.0, 0.0, 0.0)),
+            Color::new(0.0, 0.0, 0.0)
+        );
+        // Outside of the cone
+        assert_eq!(
+            light.intensity_at(Tuple::point(20.0, 0.0, 0.0)),
+            Color::new(0.0, 0.0, 0.0)
+        );
+        // Halfway through the falloff band
+        let halfway = (PI / 4.0 - PI / 16.0).tan() * 10.0;
+        assert_eq!(
+            light.intensity_at(Tuple::point(halfway, 0.0, 0.0)),
+            Color::new(0.5, 0.5, 0.5)
+        );
+    }
+
+    #[test]
+    fn test_lighting_outside_spot_light_is_black() {
+        let m = Material::default();
+        let position = Tuple::point(0.0, 0.0, 0.0);
+        let eye = Tuple::vector(0.0, 0.0, -1.0);
+        let normal = Tuple::vector(0.0, 0.0, -1.0);
+        let light = SpotLight::new(
+            Tuple::point(0.0, 0.0, -10.0),
+            Tuple::vector(0.0, 1.0, 0.0),
+            PI / 6.0,
+            0.0,
+            Color::new(1.0, 1.0, 1.0),
+        );
+
+        let actual = lighting(&m, &light, position, position, eye, normal, visible(1.0));
+        assert_eq!(actual, Color::new(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn test_lighting_partially_visible_light() {
+        let m = Material::default();
+        let position = Tuple::point(0.0, 0.0, 0.0);
+        let eye = Tuple::vector(0.0, 0.0, -1.0);
+        let normal = Tuple::vector(0.0, 0.0, -1.0);
+        let light = PointLight::new(Tuple::point(0.0, 0.0, -10.0), Color::new(1.0, 1.0, 1.0));
+
+        // Ambient is always there, diffuse and specular are halved
+        let expected = Color::new(1.0, 1.0, 1.0);
+        let actual = lighting(&m, &light, position, position, eye, normal, visible(0.5));
+
+        assert_eq!(expected, actual);
+    }
+
+    #[test]
+    fn test_attenuation_factors() {
+        let range = f64::INFINITY;
+        assert_eq!(Attenuation::None.factor(100.0, range), 1.0);
+        assert_eq!(Attenuation::Linear.factor(4.0, range), 0.25);
+        assert_eq!(Attenuation::InverseSquare.factor(4.0, range), 0.0625);
+
+        let coefficients = Attenuation::Coefficients {
+            constant: 1.0,
+            linear: 0.5,
+            quadratic: 0.25,
+        };
+        assert_eq!(coefficients.factor(2.0, range), 1.0 / 3.0);
+    }
+
+    #[test]
+    fn test_attenuation_range_cutoff() {
+        assert_eq!(Attenuation::None.factor(10.0, 10.0), 0.0);
+        assert_eq!(Attenuation::None.factor(20.0, 10.0), 0.0);
+        assert_eq!(Attenuation::None.factor(9.9, 10.0), 1.0);
+        assert_eq!(Attenuation::None.factor(5.0, 10.0), 1.0);
+        assert_eq!(Attenuation::Linear.factor(4.0, 10.0), 0.25);
+    }
+
+    #[test]
+    fn test_lighting_dims_with_distance() {
+        let m = Material::default();
+        let position = Tuple::point(0.0, 0.0, 0.0);
+        let eye = Tuple::vector(0.0, 0.0, -1.0);
+        let normal = Tuple::vector(0.0, 0.0, -1.0);
+
+        let mut near = PointLight::new(Tuple::point(0.0, 0.0, -1.0), Color::new(1.0, 1.0, 1.0));
+        near.attenuation = Attenuation::InverseSquare;
+        let mut far = PointLight::new(Tuple::point(0.0, 0.0, -100.0), Color::new(1.0, 1.0, 1.0));
+        far.attenuation = Attenuation::InverseSquare;
+
+        assert_eq!(near.attenuation_at(position), 1.0);
+        assert_eq!(
+            lighting(&m, &near, position, position, eye, normal, visible(1.0)),
+            Color::new(1.9, 1.9, 1.9)
+        );
+        assert_eq!(
+            lighting(&m, &far, position, position, eye, normal, visible(1.0)),
+            Color::new(0.00019, 0.00019, 0.00019)
+        );
+    }
+
+    #[test]
+    fn test_lighting_past_range_is_black() {
+        let m = Material::default();
+        let position = Tuple::point(0.0, 0.0, 0.0);
+        let eye = Tuple::vector(0.0, 0.0, -1.0);
+        let normal = Tuple::vector(0.0, 0.0, -1.0);
+        let mut light = PointLight::new(Tuple::point(0.0, 0.0, -10.0), Color::new(1.0, 1.0, 1.0));
+        light.range = 5.0;
+
+        let actual = lighting(&m, &light, position, position, eye, normal, visible(1.0));
+        assert_eq!(actual, Color::new(0.0, 0.0, 0.0));
+    }
 }
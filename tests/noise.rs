@@ -0,0 +1,88 @@
+#[cfg(test)]
+mod noise_tests {
+    use raytracer::noise::{perlin, Noise};
+    use raytracer::tuple::Tuple;
+
+    fn sample_points() -> Vec<Tuple> {
+        (0..200)
+            .map(|i| {
+                let t = i as f64 * 0.173;
+                Tuple::point(t - 10.0, (t * 1.7).sin() * 4.0, 3.3 - t * 0.5)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn default_noise_is_reference_perlin() {
+        let n = Noise::default();
+
+        for point in sample_points() {
+            assert_eq!(n.noise(point), perlin(point));
+        }
+    }
+
+    #[test]
+    fn same_seed_gives_same_noise() {
+        let a = Noise::new(42);
+        let b = Noise::new(42);
+
+        for point in sample_points() {
+            assert_eq!(a.noise(point), b.noise(point));
+        }
+    }
+
+    #[test]
+    fn different_seeds_give_different_noise() {
+        let a = Noise::new(1);
+        let b = Noise::new(2);
+
+        assert!(sample_points()
+            .into_iter()
+            .any(|point| a.noise(point) != b.noise(point)));
+    }
+
+    #[test]
+    fn noise_is_zero_on_lattice_points() {
+        let n = Noise::new(7);
+
+        assert_eq!(n.noise(Tuple::point(0.0, 0.0, 0.0)), 0.0);
+        assert_eq!(n.noise(Tuple::point(-4.0, 12.0, 300.0)), 0.0);
+    }
+
+    #[test]
+    fn noise_is_continuous() {
+        let n = Noise::new(7);
+        let p = Tuple::point(1.37, -2.21, 0.58);
+        let q = Tuple::point(1.37 + 1e-6, -2.21, 0.58);
+
+        assert!((n.noise(p) - n.noise(q)).abs() < 1e-4);
+    }
+
+    #[test]
+    fn single_octave_fbm_is_plain_noise() {
+        let n = Noise::new(3);
+
+        for point in sample_points() {
+            assert_eq!(n.fbm(point, 1, 2.0, 0.5), n.noise(point));
+        }
+    }
+
+    #[test]
+    fn fbm_stays_in_noise_range() {
+        let n = Noise::new(3);
+
+        for point in sample_points() {
+            assert!((-1.0..=1.0).contains(&n.fbm(point, 6, 2.0, 0.5)));
+        }
+        assert_eq!(n.fbm(Tuple::point(0.3, 0.2, 0.1), 0, 2.0, 0.5), 0.0);
+    }
+
+    #[test]
+    fn turbulence_is_between_zero_and_one() {
+        let n = Noise::new(9);
+
+        for point in sample_points() {
+            assert!((0.0..=1.0).contains(&n.turbulence(point, 5)));
+        }
+    }
+}
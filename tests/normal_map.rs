@@ -0,0 +1,145 @@
+#[cfg(test)]
+mod normal_map_tests {
+    use raytracer::colors::Color;
+    use raytracer::intersection::{prepare_computations, Intersection, Intersections};
+    use raytracer::material::Material;
+    use raytracer::noise::Noise;
+    use raytracer::normal_map::{BumpMap, NormalMap, NormalPerturbation};
+    use raytracer::plane::plane;
+    use raytracer::ray::Ray;
+    use raytracer::scene_object::MutSceneObject;
+    use raytracer::sphere::sphere;
+    use raytracer::texture::{UvMapping, UvPattern};
+    use raytracer::transformations::scale;
+    use raytracer::tuple::Tuple;
+    use raytracer::utils::EPSILON;
+    use std::sync::Arc;
+
+    // Texture that is the same tangent space normal everywhere
+    #[derive(Debug)]
+    struct FlatNormal(Color);
+
+    impl UvPattern for FlatNormal {
+        fn uv_pattern_at(&self, _u: f64, _v: f64) -> Color {
+            self.0
+        }
+    }
+
+    fn normal_map(r: f64, g: f64, b: f64, mapping: UvMapping) -> NormalMap {
+        NormalMap::new(Arc::new(FlatNormal(Color::new(r, g, b))), mapping)
+    }
+
+    fn with_perturbation(
+        object: MutSceneObject,
+        perturbation: Arc<dyn NormalPerturbation>,
+    ) -> MutSceneObject {
+        let mut m = Material::default();
+        m.normal_perturbation = Some(perturbation);
+        object.lock().unwrap().set_material(&m);
+        object
+    }
+
+    #[test]
+    fn flat_height_leaves_normal_alone() {
+        let bump = BumpMap::new(Arc::new(|_| 3.0), 1.0);
+        let n = Tuple::vector(0.0, 1.0, 0.0);
+
+        assert_eq!(bump.perturb(Tuple::point(0.3, 0.0, -2.0), n), n);
+    }
+
+    #[test]
+    fn bump_tilts_normal_down_the_slope() {
+        let bump = BumpMap::new(Arc::new(|p: Tuple| p.x), 0.5);
+        let n = bump.perturb(Tuple::point(0.0, 0.0, 0.0), Tuple::vector(0.0, 1.0, 0.0));
+
+        assert_eq!(n, Tuple::vector(-0.5, 1.0, 0.0).normalize());
+    }
+
+    #[test]
+    fn height_changing_along_the_normal_does_not_tilt() {
+        let bump = BumpMap::new(Arc::new(|p: Tuple| p.y * 10.0), 1.0);
+        let n = Tuple::vector(0.0, 1.0, 0.0);
+
+        assert_eq!(bump.perturb(Tuple::point(1.0, 0.0, 1.0), n), n);
+    }
+
+    #[test]
+    fn noise_bumps_vary_over_the_surface() {
+        let bump = BumpMap::noise(Noise::new(4), 4.0, 0.3);
+        let n = Tuple::vector(0.0, 1.0, 0.0);
+
+        let a = bump.perturb(Tuple::point(0.13, 0.0, 0.71), n);
+        let b = bump.perturb(Tuple::point(0.52, 0.0, 0.29), n);
+        assert_ne!(a, n);
+        assert_ne!(a, b);
+        assert!((a.magnitude() - 1.0).abs() < EPSILON);
+    }
+
+    #[test]
+    fn straight_up_normal_map_leaves_normal_alone() {
+        let map = normal_map(0.5, 0.5, 1.0, UvMapping::Planar);
+        let n = Tuple::vector(0.0, 1.0, 0.0);
+
+        assert_eq!(map.perturb(Tuple::point(0.25, 0.0, 0.75), n), n);
+    }
+
+    #[test]
+    fn normal_map_uses_tangent_space() {
+        // Planar mapping has u along x and v along z
+        let along_u = normal_map(1.0, 0.5, 0.5, UvMapping::Planar);
+        let along_v = normal_map(0.5, 1.0, 0.5, UvMapping::Planar);
+        let n = Tuple::vector(0.0, 1.0, 0.0);
+        let p = Tuple::point(0.25, 0.0, 0.75);
+
+        assert_eq!(along_u.perturb(p, n), Tuple::vector(1.0, 0.0, 0.0));
+        assert_eq!(along_v.perturb(p, n), Tuple::vector(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn normal_map_follows_curved_surfaces() {
+        // Facing -z on a sphere, u grows towards +x and v towards +y
+        let along_u = normal_map(1.0, 0.5, 0.5, UvMapping::Spherical);
+        let along_v = normal_map(0.5, 1.0, 0.5, UvMapping::Spherical);
+        let p = Tuple::point(0.0, 0.0, -1.0);
+        let n = Tuple::vector(0.0, 0.0, -1.0);
+
+        assert_eq!(along_u.perturb(p, n), Tuple::vector(1.0, 0.0, 0.0));
+        assert_eq!(along_v.perturb(p, n), Tuple::vector(0.0, 1.0, 0.0));
+    }
+
+    #[test]
+    fn normal_at_applies_material_perturbation() {
+        let bump = Arc::new(BumpMap::new(Arc::new(|p: Tuple| p.x), 0.5));
+        let p = with_perturbation(plane(), bump);
+
+        let n = p.lock().unwrap().normal_at(Tuple::point(0.0, 0.0, 0.0));
+        assert_eq!(n, Tuple::vector(-0.5, 1.0, 0.0).normalize());
+    }
+
+    #[test]
+    fn perturbation_happens_in_object_space() {
+        let bump = Arc::new(BumpMap::new(Arc::new(|p: Tuple| p.x), 0.5));
+        let s = with_perturbation(sphere(), bump);
+        s.lock().unwrap().set_transformation(&scale(2.0, 2.0, 2.0));
+
+        // Object space normal (-0.5, 1, 0) only gets scaled uniformly on the way to world space
+        let n = s.lock().unwrap().normal_at(Tuple::point(0.0, 2.0, 0.0));
+        assert_eq!(n, Tuple::vector(-0.5, 1.0, 0.0).normalize());
+    }
+
+    #[test]
+    fn over_point_follows_perturbed_normal() {
+        let bump = Arc::new(BumpMap::new(Arc::new(|p: Tuple| p.x), 1.0));
+        let p = with_perturbation(plane(), bump);
+        let r = Ray::new(Tuple::point(0.0, 1.0, 0.0), Tuple::vector(0.0, -1.0, 0.0));
+        let i = Arc::new(Intersection::new(1.0, p));
+
+        let comps = prepare_computations(i.clone(), r, &Intersections::from(vec![i]));
+        let expected = Tuple::vector(-1.0, 1.0, 0.0).normalize();
+        assert_eq!(comps.normal_vector, expected);
+        assert_eq!(
+            comps.over_point,
+            comps.point.add(expected.scalar_mul(EPSILON))
+        );
+    }
+}
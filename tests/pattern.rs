@@ -0,0 +1,321 @@
+#[cfg(test)]
+mod pattern_tests {
+    use raytracer::colors::Color;
+    use raytracer::intersection::{prepare_computations, Intersection, Intersections};
+    use raytracer::light::{lighting, PointLight};
+    use raytracer::material::Material;
+    use raytracer::noise::{perlin, Noise};
+    use raytracer::pattern::{
+        solid, BlendedPattern, CheckerPattern, GradientPattern, MarblePattern, Pattern,
+        PerturbedPattern, RingPattern, StripePattern, WoodPattern,
+    };
+    use raytracer::ray::Ray;
+    use raytracer::sphere::sphere;
+    use raytracer::transformations::{rotate_y, scale, translate};
+    use raytracer::tuple::Tuple;
+    use std::f64::consts::FRAC_PI_2;
+    use std::sync::Arc;
+
+    fn white() -> Color {
+        Color::new(1.0, 1.0, 1.0)
+    }
+
+    fn black() -> Color {
+        Color::new(0.0, 0.0, 0.0)
+    }
+
+    #[test]
+    fn stripe_is_constant_in_y_and_z() {
+        let p = StripePattern::new(white(), black());
+
+        for point in [
+            Tuple::point(0.0, 0.0, 0.0),
+            Tuple::point(0.0, 1.0, 0.0),
+            Tuple::point(0.0, 2.0, 0.0),
+            Tuple::point(0.0, 0.0, 1.0),
+            Tuple::point(0.0, 0.0, 2.0),
+        ] {
+            assert_eq!(p.pattern_at(point), white());
+        }
+    }
+
+    #[test]
+    fn stripe_alternates_in_x() {
+        let p = StripePattern::new(white(), black());
+
+        assert_eq!(p.pattern_at(Tuple::point(0.0, 0.0, 0.0)), white());
+        assert_eq!(p.pattern_at(Tuple::point(0.9, 0.0, 0.0)), white());
+        assert_eq!(p.pattern_at(Tuple::point(1.0, 0.0, 0.0)), black());
+        assert_eq!(p.pattern_at(Tuple::point(-0.1, 0.0, 0.0)), black());
+        assert_eq!(p.pattern_at(Tuple::point(-1.0, 0.0, 0.0)), black());
+        assert_eq!(p.pattern_at(Tuple::point(-1.1, 0.0, 0.0)), white());
+    }
+
+    #[test]
+    fn gradient_interpolates_between_colors() {
+        let p = GradientPattern::new(white(), black());
+
+        assert_eq!(p.pattern_at(Tuple::point(0.0, 0.0, 0.0)), white());
+        assert_eq!(
+            p.pattern_at(Tuple::point(0.25, 0.0, 0.0)),
+            Color::new(0.75, 0.75, 0.75)
+        );
+        assert_eq!(
+            p.pattern_at(Tuple::point(0.5, 0.0, 0.0)),
+            Color::new(0.5, 0.5, 0.5)
+        );
+        assert_eq!(
+            p.pattern_at(Tuple::point(0.75, 0.0, 0.0)),
+            Color::new(0.25, 0.25, 0.25)
+        );
+    }
+
+    #[test]
+    fn ring_extends_in_x_and_z() {
+        let p = RingPattern::new(white(), black());
+
+        assert_eq!(p.pattern_at(Tuple::point(0.0, 0.0, 0.0)), white());
+        assert_eq!(p.pattern_at(Tuple::point(1.0, 0.0, 0.0)), black());
+        assert_eq!(p.pattern_at(Tuple::point(0.0, 0.0, 1.0)), black());
+        assert_eq!(p.pattern_at(Tuple::point(0.708, 0.0, 0.708)), black());
+    }
+
+    #[test]
+    fn checkers_repeat_in_every_dimension() {
+        let p = CheckerPattern::new(white(), black());
+
+        assert_eq!(p.pattern_at(Tuple::point(0.0, 0.0, 0.0)), white());
+        assert_eq!(p.pattern_at(Tuple::point(0.99, 0.0, 0.0)), white());
+        assert_eq!(p.pattern_at(Tuple::point(1.01, 0.0, 0.0)), black());
+        assert_eq!(p.pattern_at(Tuple::point(0.0, 0.99, 0.0)), white());
+        assert_eq!(p.pattern_at(Tuple::point(0.0, 1.01, 0.0)), black());
+        assert_eq!(p.pattern_at(Tuple::point(0.0, 0.0, 0.99)), white());
+        assert_eq!(p.pattern_at(Tuple::point(0.0, 0.0, 1.01)), black());
+    }
+
+    #[test]
+    fn pattern_transformation() {
+        let mut p = StripePattern::new(white(), black());
+        p.set_transformation(&scale(2.0, 2.0, 2.0));
+
+        assert_eq!(p.pattern_at(Tuple::point(1.5, 0.0, 0.0)), white());
+    }
+
+    #[test]
+    fn lighting_with_pattern() {
+        let mut m = Material::default();
+        m.pattern = Some(Arc::new(StripePattern::new(white(), black())));
+        m.ambient = 1.0;
+        m.diffuse = 0.0;
+        m.specular = 0.0;
+        let eye = Tuple::vector(0.0, 0.0, -1.0);
+        let normal = Tuple::vector(0.0, 0.0, -1.0);
+        let light = PointLight::new(Tuple::point(0.0, 0.0, -10.0), white());
+
+        let p1 = Tuple::point(0.9, 0.0, 0.0);
+        let p2 = Tuple::point(1.1, 0.0, 0.0);
+        assert_eq!(lighting(&m, &light, p1, p1, eye, normal, white()), white());
+        assert_eq!(lighting(&m, &light, p2, p2, eye, normal, white()), black());
+    }
+
+    #[test]
+    fn pattern_follows_object_and_pattern_transforms() {
+        let s = sphere();
+        s.lock().unwrap().set_transformation(&scale(2.0, 2.0, 2.0));
+        let mut p = StripePattern::new(white(), black());
+        p.set_transformation(&translate(0.5, 0.0, 0.0));
+        let mut m = Material::default();
+        m.pattern = Some(Arc::new(p));
+        s.lock().unwrap().set_material(&m);
+
+        // Hits the scaled sphere at x = 2 in world space, which is x = 1 in object space and
+        // x = 0.5 in pattern space
+        let r = Ray::new_flat(5.0, 0.0, 0.0, -1.0, 0.0, 0.0);
+        let i = Arc::new(Intersection::new(3.0, s.clone()));
+        let comps = prepare_computations(i.clone(), r, &Intersections::from(vec![i]));
+
+        assert_eq!(comps.object_point, Tuple::point(1.0, 0.0, 0.0));
+        let material = s.lock().unwrap().material();
+        assert_eq!(material.color_at(comps.object_point), white());
+    }
+
+    #[test]
+    fn checker_of_stripes() {
+        let red = Color::new(1.0, 0.0, 0.0);
+        let blue = Color::new(0.0, 0.0, 1.0);
+        let mut stripes = StripePattern::new(red, blue);
+        stripes.set_transformation(&scale(0.5, 0.5, 0.5));
+        let p = CheckerPattern::nested(Arc::new(stripes), solid(black()));
+
+        // Inside the first checker the stripes alternate every half unit
+        assert_eq!(p.pattern_at(Tuple::point(0.25, 0.0, 0.0)), red);
+        assert_eq!(p.pattern_at(Tuple::point(0.75, 0.0, 0.0)), blue);
+        assert_eq!(p.pattern_at(Tuple::point(1.25, 0.0, 0.0)), black());
+        assert_eq!(p.pattern_at(Tuple::point(1.25, 1.0, 0.0)), red);
+    }
+
+    #[test]
+    fn nested_patterns_use_parent_pattern_space() {
+        let stripes = Arc::new(StripePattern::new(white(), black()));
+        let mut p = CheckerPattern::nested(stripes, solid(black()));
+        p.set_transformation(&scale(2.0, 2.0, 2.0));
+
+        // x = 1.5 is x = 0.75 in the checker's space, so the stripes see x = 0.75 too
+        assert_eq!(p.pattern_at(Tuple::point(1.5, 0.0, 0.0)), white());
+        assert_eq!(p.pattern_at(Tuple::point(2.5, 2.0, 0.0)), black());
+    }
+
+    #[test]
+    fn gradient_between_patterns() {
+        let stripes = Arc::new(StripePattern::new(white(), black()));
+        let p = GradientPattern::nested(solid(black()), stripes);
+
+        assert_eq!(
+            p.pattern_at(Tuple::point(0.25, 0.0, 0.0)),
+            Color::new(0.25, 0.25, 0.25)
+        );
+        assert_eq!(p.pattern_at(Tuple::point(1.25, 0.0, 0.0)), black());
+    }
+
+    #[test]
+    fn blended_pattern_averages_both_patterns() {
+        let horizontal = Arc::new(StripePattern::new(white(), black()));
+        let mut vertical = StripePattern::new(white(), black());
+        vertical.set_transformation(&rotate_y(FRAC_PI_2));
+        let p = BlendedPattern::new(horizontal, Arc::new(vertical));
+
+        assert_eq!(p.pattern_at(Tuple::point(0.5, 0.0, -0.5)), white());
+        assert_eq!(
+            p.pattern_at(Tuple::point(1.5, 0.0, -0.5)),
+            Color::new(0.5, 0.5, 0.5)
+        );
+        assert_eq!(
+            p.pattern_at(Tuple::point(0.5, 0.0, 0.5)),
+            Color::new(0.5, 0.5, 0.5)
+        );
+        assert_eq!(p.pattern_at(Tuple::point(1.5, 0.0, 0.5)), black());
+    }
+
+    #[test]
+    fn perlin_noise_is_zero_on_lattice_and_bounded() {
+        assert_eq!(perlin(Tuple::point(0.0, 0.0, 0.0)), 0.0);
+        assert_eq!(perlin(Tuple::point(3.0, -2.0, 7.0)), 0.0);
+
+        for i in 0..100 {
+            let t = i as f64 * 0.37;
+            let n = perlin(Tuple::point(t, t * 0.5 - 3.0, 1.3 - t));
+            assert!((-1.0..=1.0).contains(&n));
+            assert_eq!(n, perlin(Tuple::point(t, t * 0.5 - 3.0, 1.3 - t)));
+        }
+    }
+
+    #[test]
+    fn unscaled_perturbation_leaves_pattern_unchanged() {
+        let stripes = Arc::new(StripePattern::new(white(), black()));
+        let p = PerturbedPattern::new(stripes.clone(), 0.0);
+
+        for x in [0.1, 0.9, 1.1, -0.1, -1.1] {
+            let point = Tuple::point(x, 0.3, 0.7);
+            assert_eq!(p.pattern_at(point), stripes.pattern_at(point));
+        }
+    }
+
+    #[test]
+    fn perturbation_jitters_the_lookup_point() {
+        let gradient = Arc::new(GradientPattern::new(white(), black()));
+        let p = PerturbedPattern::new(gradient.clone(), 0.2);
+
+        // Noise vanishes at lattice points, so those lookups are left alone
+        let lattice = Tuple::point(0.0, 1.0, 2.0);
+        assert_eq!(p.pattern_at(lattice), gradient.pattern_at(lattice));
+
+        let point = Tuple::point(0.3, 0.45, 0.6);
+        let jittered = p.pattern_at(point);
+        assert_ne!(jittered, gradient.pattern_at(point));
+        assert_eq!(jittered, p.pattern_at(point));
+    }
+
+    #[test]
+    fn lighting_with_nested_pattern() {
+        let mut m = Material::default();
+        m.pattern = Some(Arc::new(CheckerPattern::nested(
+            Arc::new(StripePattern::new(white(), black())),
+            solid(black()),
+        )));
+        m.ambient = 1.0;
+        m.diffuse = 0.0;
+        m.specular = 0.0;
+        let eye = Tuple::vector(0.0, 0.0, -1.0);
+        let normal = Tuple::vector(0.0, 0.0, -1.0);
+        let light = PointLight::new(Tuple::point(0.0, 0.0, -10.0), white());
+
+        let p1 = Tuple::point(0.5, 0.0, 0.0);
+        let p2 = Tuple::point(0.5, 1.5, 0.0);
+        assert_eq!(lighting(&m, &light, p1, p1, eye, normal, white()), white());
+        assert_eq!(lighting(&m, &light, p2, p2, eye, normal, white()), black());
+    }
+
+    #[test]
+    fn marble_without_turbulence_is_a_sine_wave() {
+        let mut p = MarblePattern::new(white(), black());
+        p.turbulence = 0.0;
+
+        assert_eq!(
+            p.pattern_at(Tuple::point(0.0, 0.0, 0.0)),
+            Color::new(0.5, 0.5, 0.5)
+        );
+        assert_eq!(p.pattern_at(Tuple::point(0.5, 3.0, 2.0)), black());
+        assert_eq!(p.pattern_at(Tuple::point(1.5, -1.0, 0.0)), white());
+    }
+
+    #[test]
+    fn marble_veins_are_bent_by_noise() {
+        let mut p = MarblePattern::new(white(), black());
+        p.noise = Noise::new(11);
+        let mut straight = p.clone();
+        straight.turbulence = 0.0;
+
+        let point = Tuple::point(0.3, 0.45, 0.6);
+        assert_ne!(p.pattern_at(point), straight.pattern_at(point));
+    }
+
+    #[test]
+    fn wood_without_turbulence_has_regular_rings() {
+        let mut p = WoodPattern::new(white(), black());
+        p.turbulence = 0.0;
+
+        assert_eq!(p.pattern_at(Tuple::point(0.0, 0.0, 0.0)), white());
+        assert_eq!(
+            p.pattern_at(Tuple::point(0.125, 0.0, 0.0)),
+            Color::new(0.5, 0.5, 0.5)
+        );
+        assert_eq!(
+            p.pattern_at(Tuple::point(0.0, 7.0, 0.375)),
+            Color::new(0.5, 0.5, 0.5)
+        );
+    }
+
+    #[test]
+    fn seeded_procedural_patterns_are_reproducible() {
+        let mut a = WoodPattern::new(white(), black());
+        a.noise = Noise::new(5);
+        let mut b = WoodPattern::new(white(), black());
+        b.noise = Noise::new(5);
+
+        for i in 0..20 {
+            let point = Tuple::point(i as f64 * 0.31, 0.2, i as f64 * -0.17);
+            assert_eq!(a.pattern_at(point), b.pattern_at(point));
+        }
+    }
+
+    #[test]
+    fn marble_as_material_color_source() {
+        let mut marble = MarblePattern::new(white(), black());
+        marble.turbulence = 0.0;
+        let mut m = Material::default();
+        m.pattern = Some(Arc::new(marble));
+
+        assert_eq!(m.color_at(Tuple::point(0.5, 0.0, 0.0)), black());
+        assert_eq!(m.color_at(Tuple::point(-0.5, 0.0, 0.0)), white());
+    }
+}
@@ -0,0 +1,62 @@
+#[cfg(test)]
+mod plane_tests {
+    use raytracer::intersection::Intersection;
+    use raytracer::plane::plane;
+    use raytracer::ray::Ray;
+    use raytracer::tuple::Tuple;
+
+    #[test]
+    fn normal_is_constant_everywhere() {
+        let p = plane();
+        let expected = Tuple::vector(0.0, 1.0, 0.0);
+
+        let guard = p.lock().unwrap();
+        assert_eq!(guard.local_normal_at(Tuple::point(0.0, 0.0, 0.0)), expected);
+        assert_eq!(
+            guard.local_normal_at(Tuple::point(10.0, 0.0, -10.0)),
+            expected
+        );
+        assert_eq!(
+            guard.local_normal_at(Tuple::point(-5.0, 0.0, 150.0)),
+            expected
+        );
+    }
+
+    #[test]
+    fn intersect_parallel_ray() {
+        let p = plane();
+        let r = Ray::new_flat(0.0, 10.0, 0.0, 0.0, 0.0, 1.0);
+
+        let xs = p.lock().unwrap().local_intersect(r, p.clone());
+        assert_eq!(xs.len(), 0);
+    }
+
+    #[test]
+    fn intersect_coplanar_ray() {
+        let p = plane();
+        let r = Ray::new_flat(0.0, 0.0, 0.0, 0.0, 0.0, 1.0);
+
+        let xs = p.lock().unwrap().local_intersect(r, p.clone());
+        assert_eq!(xs.len(), 0);
+    }
+
+    #[test]
+    fn intersect_from_above() {
+        let p = plane();
+        let r = Ray::new_flat(0.0, 1.0, 0.0, 0.0, -1.0, 0.0);
+
+        let xs = p.lock().unwrap().local_intersect(r, p.clone());
+        assert_eq!(xs.len(), 1);
+        assert_eq!(xs[0], Intersection::new(1.0, p.clone()));
+    }
+
+    #[test]
+    fn intersect_from_below() {
+        let p = plane();
+        let r = Ray::new_flat(0.0, -1.0, 0.0, 0.0, 1.0, 0.0);
+
+        let xs = p.lock().unwrap().local_intersect(r, p.clone());
+        assert_eq!(xs.len(), 1);
+        assert_eq!(xs[0], Intersection::new(1.0, p.clone()));
+    }
+}
@@ -37,7 +37,7 @@ mod ray_tests {
         let ray = Ray::new_flat(0.0, 0.0, -5.0, 0.0, 0.0, 1.0);
         let s = sphere();
 
-        let res = ray.intersect(s.clone());
+        let res = s.lock().unwrap().local_intersect(ray, s.clone());
 
         assert_eq!(res.len(), 2);
         assert_eq!(res[0], Intersection::new(4.0, s.clone()));
@@ -49,7 +49,7 @@ mod ray_tests {
         let ray = Ray::new_flat(0.0, 1.0, -5.0, 0.0, 0.0, 1.0);
         let s = sphere();
 
-        let res = ray.intersect(s.clone());
+        let res = s.lock().unwrap().local_intersect(ray, s.clone());
 
         assert_eq!(res.len(), 2);
         assert_eq!(res[0], Intersection::new(5.0, s.clone()));
@@ -61,7 +61,7 @@ mod ray_tests {
         let ray = Ray::new_flat(0.0, 2.0, -5.0, 0.0, 0.0, 1.0);
         let s = sphere();
 
-        let res = ray.intersect(s.clone());
+        let res = s.lock().unwrap().local_intersect(ray, s.clone());
         assert_eq!(res.len(), 0)
     }
 
@@ -70,7 +70,7 @@ mod ray_tests {
         let ray = Ray::new_flat(0.0, 0.0, 0.0, 0.0, 0.0, 1.0);
         let s = sphere();
 
-        let res = ray.intersect(s.clone());
+        let res = s.lock().unwrap().local_intersect(ray, s.clone());
 
         assert_eq!(res[0], Intersection::new(-1.0, s.clone()));
         assert_eq!(res[1], Intersection::new(1.0, s.clone()));
@@ -81,7 +81,7 @@ mod ray_tests {
         let ray = Ray::new_flat(0.0, 0.0, 5.0, 0.0, 0.0, 1.0);
         let s = sphere();
 
-        let res = ray.intersect(s.clone());
+        let res = s.lock().unwrap().local_intersect(ray, s.clone());
 
         assert_eq!(res[0], Intersection::new(-6.0, s.clone()));
         assert_eq!(res[1], Intersection::new(-4.0, s.clone()));
@@ -108,7 +108,7 @@ mod ray_tests {
 
         let r = Ray::new_flat(0.0, 0.0, -5.0, 0.0, 0.0, 1.0);
 
-        let xs = r.intersect(s.clone());
+        let xs = s.lock().unwrap().local_intersect(r, s.clone());
         let expected_id = s.lock().unwrap().id();
 
         assert_eq!(xs.len(), 2);
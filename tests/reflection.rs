@@ -0,0 +1,162 @@
+#[cfg(test)]
+mod reflection_tests {
+    use raytracer::colors::Color;
+    use raytracer::intersection::{
+        color_at, prepare_computations, reflected_color, shade_hit, Intersection, Intersections,
+    };
+    use raytracer::light::PointLight;
+    use raytracer::material::Material;
+    use raytracer::plane::plane;
+    use raytracer::ray::Ray;
+    use raytracer::scene_object::MutSceneObject;
+    use raytracer::transformations::translate;
+    use raytracer::tuple::Tuple;
+    use raytracer::world::{World, DEFAULT_MAX_DEPTH};
+    use std::f64::consts::{FRAC_1_SQRT_2, SQRT_2};
+    use std::sync::Arc;
+
+    fn reflective_plane(reflective: f64, y: f64) -> MutSceneObject {
+        let p = plane();
+        let mut m = Material::default();
+        m.reflective = reflective;
+        p.lock().unwrap().set_material(&m);
+        p.lock()
+            .unwrap()
+            .set_transformation(&translate(0.0, y, 0.0));
+        p
+    }
+
+    #[test]
+    fn default_material_is_not_reflective() {
+        assert_eq!(Material::default().reflective, 0.0);
+    }
+
+    #[test]
+    fn precompute_reflect_vector() {
+        let p = plane();
+        let r = Ray::new(
+            Tuple::point(0.0, 1.0, -1.0),
+            Tuple::vector(0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+        );
+        let i = Arc::new(Intersection::new(SQRT_2, p));
+
+        let comps = prepare_computations(i.clone(), r, &Intersections::from(vec![i]));
+        assert_eq!(
+            comps.reflect_vector,
+            Tuple::vector(0.0, FRAC_1_SQRT_2, FRAC_1_SQRT_2)
+        );
+    }
+
+    #[test]
+    fn reflected_color_of_nonreflective_material() {
+        let w = World::default_world();
+        let r = Ray::new(Tuple::point(0.0, 0.0, 0.0), Tuple::vector(0.0, 0.0, 1.0));
+        let shape = w.objects[1].clone();
+        let mut m = shape.lock().unwrap().material();
+        m.ambient = 1.0;
+        shape.lock().unwrap().set_material(&m);
+        let i = Arc::new(Intersection::new(1.0, shape));
+
+        let comps = prepare_computations(i.clone(), r, &Intersections::from(vec![i]));
+        assert_eq!(
+            reflected_color(Arc::new(w), &comps, DEFAULT_MAX_DEPTH),
+            Color::new(0.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn reflected_color_of_reflective_material() {
+        let mut w = World::default_world();
+        let shape = reflective_plane(0.5, -1.0);
+        w.objects.push(shape.clone());
+        let r = Ray::new(
+            Tuple::point(0.0, 0.0, -3.0),
+            Tuple::vector(0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+        );
+        let i = Arc::new(Intersection::new(SQRT_2, shape));
+
+        let comps = prepare_computations(i.clone(), r, &Intersections::from(vec![i]));
+        assert_eq!(
+            reflected_color(Arc::new(w), &comps, DEFAULT_MAX_DEPTH),
+            Color::new(0.19033, 0.23791, 0.14274)
+        );
+    }
+
+    #[test]
+    fn shade_hit_includes_reflection() {
+        let mut w = World::default_world();
+        let shape = reflective_plane(0.5, -1.0);
+        w.objects.push(shape.clone());
+        let r = Ray::new(
+            Tuple::point(0.0, 0.0, -3.0),
+            Tuple::vector(0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+        );
+        let i = Arc::new(Intersection::new(SQRT_2, shape));
+
+        let comps = prepare_computations(i.clone(), r, &Intersections::from(vec![i]));
+        assert_eq!(
+            shade_hit(Arc::new(w), &comps, DEFAULT_MAX_DEPTH),
+            Color::new(0.87676, 0.92434, 0.82918)
+        );
+    }
+
+    #[test]
+    fn mutually_reflective_surfaces_terminate() {
+        let mut w = World::new();
+        w.light_sources.push(Box::new(PointLight::new(
+            Tuple::point(0.0, 0.0, 0.0),
+            Color::new(1.0, 1.0, 1.0),
+        )));
+        w.objects.push(reflective_plane(1.0, -1.0));
+        w.objects.push(reflective_plane(1.0, 1.0));
+        let r = Ray::new(Tuple::point(0.0, 0.0, 0.0), Tuple::vector(0.0, 1.0, 0.0));
+
+        // Only has to return; every bounce adds the same lit surface
+        let c = color_at(Arc::new(w), r, DEFAULT_MAX_DEPTH);
+        assert!(c.red() > 0.0);
+    }
+
+    #[test]
+    fn reflected_color_at_maximum_depth() {
+        let mut w = World::default_world();
+        let shape = reflective_plane(0.5, -1.0);
+        w.objects.push(shape.clone());
+        let r = Ray::new(
+            Tuple::point(0.0, 0.0, -3.0),
+            Tuple::vector(0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+        );
+        let i = Arc::new(Intersection::new(SQRT_2, shape));
+
+        let comps = prepare_computations(i.clone(), r, &Intersections::from(vec![i]));
+        assert_eq!(
+            reflected_color(Arc::new(w), &comps, 0),
+            Color::new(0.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn configured_depth_limits_bounces() {
+        let mut w = World::default_world();
+        w.max_depth = 0;
+        let shape = reflective_plane(0.5, -1.0);
+        w.objects.push(shape.clone());
+        let w = Arc::new(w);
+        let r = Ray::new(
+            Tuple::point(0.0, 0.0, -3.0),
+            Tuple::vector(0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+        );
+        let i = Arc::new(Intersection::new(SQRT_2, shape));
+
+        // Without bounces only the plane's own surface shading is left
+        let comps = prepare_computations(i.clone(), r, &Intersections::from(vec![i]));
+        let surface = shade_hit(w.clone(), &comps, w.max_depth);
+        assert_eq!(
+            surface,
+            shade_hit(w.clone(), &comps, DEFAULT_MAX_DEPTH).sub(reflected_color(
+                w.clone(),
+                &comps,
+                DEFAULT_MAX_DEPTH
+            ))
+        );
+    }
+}
@@ -0,0 +1,450 @@
+#[cfg(test)]
+mod refraction_tests {
+    use raytracer::colors::Color;
+    use raytracer::group::group;
+    use raytracer::intersection::{
+        intersect_world, prepare_computations, reflected_color, refracted_color, schlick,
+        shade_hit, Intersection, Intersections,
+    };
+    use raytracer::light::PointLight;
+    use raytracer::material::Material;
+    use raytracer::matrix::Matrix;
+    use raytracer::pattern::Pattern;
+    use raytracer::plane::plane;
+    use raytracer::ray::Ray;
+    use raytracer::scene_object::MutSceneObject;
+    use raytracer::sphere::{glass_sphere, sphere};
+    use raytracer::transformations::{scale, translate};
+    use raytracer::tuple::Tuple;
+    use raytracer::utils::EPSILON;
+    use raytracer::world::{World, DEFAULT_MAX_DEPTH};
+    use std::f64::consts::{FRAC_1_SQRT_2, SQRT_2};
+    use std::sync::Arc;
+
+    // Pattern whose color is the point it is looked up at
+    #[derive(Debug)]
+    struct PointPattern {
+        transform: Matrix,
+    }
+
+    impl Pattern for PointPattern {
+        fn transformation(&self) -> Matrix {
+            self.transform.clone()
+        }
+
+        fn set_transformation(&mut self, transform: &Matrix) {
+            self.transform = transform.clone();
+        }
+
+        fn local_pattern_at(&self, point: Tuple) -> Color {
+            Color::new(point.x, point.y, point.z)
+        }
+    }
+
+    fn intersections(xs: Vec<(f64, MutSceneObject)>) -> Intersections {
+        Intersections::from(
+            xs.into_iter()
+                .map(|(t, object)| Arc::new(Intersection::new(t, object)))
+                .collect::<Vec<_>>(),
+        )
+    }
+
+    fn set_refractive_index(object: &MutSceneObject, refractive_index: f64) {
+        let mut m = object.lock().unwrap().material();
+        m.refractive_index = refractive_index;
+        object.lock().unwrap().set_material(&m);
+    }
+
+    // Default world with a transparent plane below the spheres and a red ball under the plane
+    fn world_with_transparent_floor(reflective: f64) -> (World, MutSceneObject) {
+        let mut w = World::default_world();
+        let floor = plane();
+        floor
+            .lock()
+            .unwrap()
+            .set_transformation(&translate(0.0, -1.0, 0.0));
+        let mut m = Material::default();
+        m.reflective = reflective;
+        m.transparency = 0.5;
+        m.refractive_index = 1.5;
+        floor.lock().unwrap().set_material(&m);
+        w.objects.push(floor.clone());
+
+        let ball = sphere();
+        let mut m = Material::default();
+        m.color = Color::new(1.0, 0.0, 0.0);
+        m.ambient = 0.5;
+        ball.lock().unwrap().set_material(&m);
+        ball.lock()
+            .unwrap()
+            .set_transformation(&translate(0.0, -3.5, -0.5));
+        w.objects.push(ball);
+
+        (w, floor)
+    }
+
+    #[test]
+    fn default_material_is_opaque() {
+        let m = Material::default();
+
+        assert_eq!(m.transparency, 0.0);
+        assert_eq!(m.refractive_index, 1.0);
+    }
+
+    #[test]
+    fn glass_sphere_is_transparent() {
+        let m = glass_sphere().lock().unwrap().material();
+
+        assert_eq!(m.transparency, 1.0);
+        assert_eq!(m.refractive_index, 1.5);
+    }
+
+    #[test]
+    fn n1_and_n2_at_every_intersection() {
+        let a = glass_sphere();
+        a.lock().unwrap().set_transformation(&scale(2.0, 2.0, 2.0));
+        set_refractive_index(&a, 1.5);
+        let b = glass_sphere();
+        b.lock()
+            .unwrap()
+            .set_transformation(&translate(0.0, 0.0, -0.25));
+        set_refractive_index(&b, 2.0);
+        let c = glass_sphere();
+        c.lock()
+            .unwrap()
+            .set_transformation(&translate(0.0, 0.0, 0.25));
+        set_refractive_index(&c, 2.5);
+        let r = Ray::new(Tuple::point(0.0, 0.0, -4.0), Tuple::vector(0.0, 0.0, 1.0));
+        let xs = intersections(vec![
+            (2.0, a.clone()),
+            (2.75, b.clone()),
+            (3.25, c.clone()),
+            (4.75, b),
+            (5.25, c),
+            (6.0, a),
+        ]);
+
+        let expected = [
+            (1.0, 1.5),
+            (1.5, 2.0),
+            (2.0, 2.5),
+            (2.5, 2.5),
+            (2.5, 1.5),
+            (1.5, 1.0),
+        ];
+        for (i, (n1, n2)) in expected.iter().enumerate() {
+            let comps = prepare_computations(xs.values[i].clone(), r, &xs);
+            assert_eq!(comps.n1, *n1);
+            assert_eq!(comps.n2, *n2);
+        }
+    }
+
+    #[test]
+    fn n1_and_n2_through_overlapping_instances_of_one_sphere() {
+        // The same glass sphere in two groups, the second one moved into the first
+        let glass = glass_sphere();
+        let a = group(vec![glass.clone()]);
+        let b = group(vec![glass]);
+        b.lock()
+            .unwrap()
+            .set_transformation(&translate(0.0, 0.0, 1.0));
+        let mut w = World::new();
+        w.objects = vec![a, b];
+        let r = Ray::new_flat(0.0, 0.0, -4.0, 0.0, 0.0, 1.0);
+        let xs = intersect_world(Arc::new(w), r);
+
+        assert_eq!(xs.len(), 4);
+        let expected = [(1.0, 1.5), (1.5, 1.5), (1.5, 1.5), (1.5, 1.0)];
+        for (i, (n1, n2)) in expected.iter().enumerate() {
+            let comps = prepare_computations(xs.values[i].clone(), r, &xs);
+            assert_eq!(comps.n1, *n1);
+            assert_eq!(comps.n2, *n2);
+        }
+    }
+
+    #[test]
+    fn under_point_is_below_the_surface() {
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let s = glass_sphere();
+        s.lock()
+            .unwrap()
+            .set_transformation(&translate(0.0, 0.0, 1.0));
+        let xs = intersections(vec![(5.0, s)]);
+
+        let comps = prepare_computations(xs.values[0].clone(), r, &xs);
+        assert!(comps.under_point.z > EPSILON / 2.0);
+        assert!(comps.point.z < comps.under_point.z);
+    }
+
+    #[test]
+    fn refracted_color_of_opaque_surface() {
+        let w = World::default_world();
+        let shape = w.objects[0].clone();
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let xs = intersections(vec![(4.0, shape.clone()), (6.0, shape)]);
+
+        let comps = prepare_computations(xs.values[0].clone(), r, &xs);
+        assert_eq!(
+            refracted_color(Arc::new(w), &comps, DEFAULT_MAX_DEPTH),
+            Color::new(0.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn refracted_color_at_maximum_depth() {
+        let w = World::default_world();
+        let shape = w.objects[0].clone();
+        let mut m = shape.lock().unwrap().material();
+        m.transparency = 1.0;
+        m.refractive_index = 1.5;
+        shape.lock().unwrap().set_material(&m);
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let xs = intersections(vec![(4.0, shape.clone()), (6.0, shape)]);
+
+        let comps = prepare_computations(xs.values[0].clone(), r, &xs);
+        assert_eq!(
+            refracted_color(Arc::new(w), &comps, 0),
+            Color::new(0.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn refracted_color_under_total_internal_reflection() {
+        let w = World::default_world();
+        let shape = w.objects[0].clone();
+        let mut m = shape.lock().unwrap().material();
+        m.transparency = 1.0;
+        m.refractive_index = 1.5;
+        shape.lock().unwrap().set_material(&m);
+        let r = Ray::new(
+            Tuple::point(0.0, 0.0, FRAC_1_SQRT_2),
+            Tuple::vector(0.0, 1.0, 0.0),
+        );
+        let xs = intersections(vec![
+            (-FRAC_1_SQRT_2, shape.clone()),
+            (FRAC_1_SQRT_2, shape),
+        ]);
+
+        // Inside the sphere, so the second intersection is the one to look at
+        let comps = prepare_computations(xs.values[1].clone(), r, &xs);
+        assert_eq!(
+            refracted_color(Arc::new(w), &comps, DEFAULT_MAX_DEPTH),
+            Color::new(0.0, 0.0, 0.0)
+        );
+    }
+
+    #[test]
+    fn refracted_color_with_refracted_ray() {
+        let w = World::default_world();
+        let a = w.objects[0].clone();
+        let mut m = a.lock().unwrap().material();
+        m.ambient = 1.0;
+        m.pattern = Some(Arc::new(PointPattern {
+            transform: Matrix::identity_matrix(4),
+        }));
+        a.lock().unwrap().set_material(&m);
+        let b = w.objects[1].clone();
+        let mut m = b.lock().unwrap().material();
+        m.transparency = 1.0;
+        m.refractive_index = 1.5;
+        b.lock().unwrap().set_material(&m);
+        let r = Ray::new(Tuple::point(0.0, 0.0, 0.1), Tuple::vector(0.0, 1.0, 0.0));
+        let xs = intersections(vec![
+            (-0.9899, a.clone()),
+            (-0.4899, b.clone()),
+            (0.4899, b),
+            (0.9899, a),
+        ]);
+
+        let comps = prepare_computations(xs.values[2].clone(), r, &xs);
+        assert_eq!(
+            refracted_color(Arc::new(w), &comps, DEFAULT_MAX_DEPTH),
+            Color::new(0.0, 0.99888, 0.04722)
+        );
+    }
+
+    #[test]
+    fn shade_hit_with_transparent_material() {
+        let (w, floor) = world_with_transparent_floor(0.0);
+        let r = Ray::new(
+            Tuple::point(0.0, 0.0, -3.0),
+            Tuple::vector(0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+        );
+        let xs = intersections(vec![(SQRT_2, floor)]);
+
+        // The floor only casts half a shadow, so the red ball below it is lit as well
+        let comps = prepare_computations(xs.values[0].clone(), r, &xs);
+        assert_eq!(
+            shade_hit(Arc::new(w), &comps, DEFAULT_MAX_DEPTH),
+            Color::new(1.12547, 0.68643, 0.68643)
+        );
+    }
+
+    #[test]
+    fn schlick_under_total_internal_reflection() {
+        let shape = glass_sphere();
+        let r = Ray::new(
+            Tuple::point(0.0, 0.0, FRAC_1_SQRT_2),
+            Tuple::vector(0.0, 1.0, 0.0),
+        );
+        let xs = intersections(vec![
+            (-FRAC_1_SQRT_2, shape.clone()),
+            (FRAC_1_SQRT_2, shape),
+        ]);
+
+        let comps = prepare_computations(xs.values[1].clone(), r, &xs);
+        assert_eq!(schlick(&comps), 1.0);
+    }
+
+    #[test]
+    fn schlick_with_perpendicular_ray() {
+        let shape = glass_sphere();
+        let r = Ray::new(Tuple::point(0.0, 0.0, 0.0), Tuple::vector(0.0, 1.0, 0.0));
+        let xs = intersections(vec![(-1.0, shape.clone()), (1.0, shape)]);
+
+        let comps = prepare_computations(xs.values[1].clone(), r, &xs);
+        assert!((schlick(&comps) - 0.04).abs() < EPSILON);
+    }
+
+    #[test]
+    fn schlick_with_small_angle_and_n2_greater_than_n1() {
+        let shape = glass_sphere();
+        let r = Ray::new(Tuple::point(0.0, 0.99, -2.0), Tuple::vector(0.0, 0.0, 1.0));
+        let xs = intersections(vec![(1.8589, shape)]);
+
+        let comps = prepare_computations(xs.values[0].clone(), r, &xs);
+        assert!((schlick(&comps) - 0.48873).abs() < EPSILON);
+    }
+
+    #[test]
+    fn shade_hit_with_reflective_transparent_material() {
+        let (w, floor) = world_with_transparent_floor(0.5);
+        let r = Ray::new(
+            Tuple::point(0.0, 0.0, -3.0),
+            Tuple::vector(0.0, -FRAC_1_SQRT_2, FRAC_1_SQRT_2),
+        );
+        let xs = intersections(vec![(SQRT_2, floor)]);
+
+        let comps = prepare_computations(xs.values[0].clone(), r, &xs);
+        assert_eq!(
+            shade_hit(Arc::new(w), &comps, DEFAULT_MAX_DEPTH),
+            Color::new(1.115, 0.69643, 0.69243)
+        );
+    }
+
+    // Lit world holding just a glass sphere with the given absorption, and a ray straight at it
+    fn absorbing_sphere(absorption: Color) -> (Arc<World>, MutSceneObject, Ray) {
+        let s = glass_sphere();
+        let mut m = s.lock().unwrap().material();
+        m.absorption = absorption;
+        s.lock().unwrap().set_material(&m);
+        let mut w = World::new();
+        w.light_sources.push(Box::new(PointLight::new(
+            Tuple::point(-10.0, 10.0, -10.0),
+            Color::new(1.0, 1.0, 1.0),
+        )));
+        w.objects.push(s.clone());
+        let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        (Arc::new(w), s, r)
+    }
+
+    #[test]
+    fn default_material_absorbs_nothing() {
+        assert_eq!(Material::default().absorption, Color::new(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn absorption_of_the_medium_being_entered() {
+        let absorption = Color::new(0.0, 0.5, 1.0);
+        let (_, s, r) = absorbing_sphere(absorption);
+        let xs = intersections(vec![(4.0, s.clone()), (6.0, s)]);
+
+        // Going in the refracted ray travels through the sphere, coming out through empty space
+        let entering = prepare_computations(xs.values[0].clone(), r, &xs);
+        assert_eq!(entering.absorption, absorption);
+        let leaving = prepare_computations(xs.values[1].clone(), r, &xs);
+        assert_eq!(leaving.absorption, Color::new(0.0, 0.0, 0.0));
+    }
+
+    #[test]
+    fn refracted_color_is_absorbed_over_distance() {
+        let (clear_world, clear, r) = absorbing_sphere(Color::new(0.0, 0.0, 0.0));
+        let xs = intersections(vec![(4.0, clear.clone()), (6.0, clear)]);
+        let comps = prepare_computations(xs.values[0].clone(), r, &xs);
+        let unabsorbed = refracted_color(clear_world, &comps, DEFAULT_MAX_DEPTH);
+
+        let (w, s, r) = absorbing_sphere(Color::new(0.0, 0.5, 1.0));
+        let xs = intersections(vec![(4.0, s.clone()), (6.0, s)]);
+        let comps = prepare_computations(xs.values[0].clone(), r, &xs);
+
+        // Straight through the middle of the unit sphere is 2 units of glass
+        let expected = unabsorbed.mul(Color::new(1.0, (-1.0f64).exp(), (-2.0f64).exp()));
+        assert_ne!(unabsorbed, Color::new(0.0, 0.0, 0.0));
+        assert_eq!(refracted_color(w, &comps, DEFAULT_MAX_DEPTH), expected);
+    }
+
+    // Reflected color of the inside of the sphere, seen by a ray from its center along +z
+    fn internal_reflection(absorption: Color) -> Color {
+        let (w, s, _) = absorbing_sphere(absorption);
+        let mut m = s.lock().unwrap().material();
+        m.reflective = 0.5;
+        s.lock().unwrap().set_material(&m);
+        let r = Ray::new_flat(0.0, 0.0, 0.0, 0.0, 0.0, 1.0);
+        let xs = intersections(vec![(-1.0, s.clone()), (1.0, s)]);
+        let comps = prepare_computations(xs.values[1].clone(), r, &xs);
+
+        // One bounce, so the far side is shaded without reflecting or refracting any further
+        reflected_color(w, &comps, 1)
+    }
+
+    #[test]
+    fn absorption_of_the_medium_reflected_rays_travel_through() {
+        let absorption = Color::new(0.0, 0.5, 1.0);
+        let (_, s, r) = absorbing_sphere(absorption);
+        let xs = intersections(vec![(4.0, s.clone()), (6.0, s)]);
+
+        let entering = prepare_computations(xs.values[0].clone(), r, &xs);
+        assert_eq!(entering.reflected_absorption, Color::new(0.0, 0.0, 0.0));
+        let leaving = prepare_computations(xs.values[1].clone(), r, &xs);
+        assert_eq!(leaving.reflected_absorption, absorption);
+    }
+
+    #[test]
+    fn internal_reflection_is_absorbed_over_distance() {
+        let unabsorbed = internal_reflection(Color::new(0.0, 0.0, 0.0));
+        let absorbed = internal_reflection(Color::new(0.0, 0.5, 1.0));
+
+        // Back across the sphere from one side to the other is 2 units of glass
+        let expected = unabsorbed.mul(Color::new(1.0, (-1.0f64).exp(), (-2.0f64).exp()));
+        assert_ne!(unabsorbed, Color::new(0.0, 0.0, 0.0));
+        assert_eq!(absorbed, expected);
+    }
+
+    #[test]
+    fn outside_reflection_is_not_absorbed() {
+        let outside = |absorption: Color| {
+            let (w, s, r) = absorbing_sphere(absorption);
+            let mut m = s.lock().unwrap().material();
+            m.reflective = 0.5;
+            s.lock().unwrap().set_material(&m);
+            let backdrop = sphere();
+            backdrop
+                .lock()
+                .unwrap()
+                .set_transformation(&scale(10.0, 10.0, 10.0));
+            let mut world = World::new();
+            world.light_sources.push(Box::new(PointLight::new(
+                Tuple::point(-5.0, 5.0, -5.0),
+                Color::new(1.0, 1.0, 1.0),
+            )));
+            world.objects = vec![w.objects[0].clone(), backdrop];
+            let xs = intersections(vec![(4.0, s.clone()), (6.0, s)]);
+            let comps = prepare_computations(xs.values[0].clone(), r, &xs);
+            reflected_color(Arc::new(world), &comps, 1)
+        };
+
+        let clear = outside(Color::new(0.0, 0.0, 0.0));
+        assert_ne!(clear, Color::new(0.0, 0.0, 0.0));
+        assert_eq!(outside(Color::new(0.0, 0.5, 1.0)), clear);
+    }
+}
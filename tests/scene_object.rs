@@ -0,0 +1,111 @@
+#[cfg(test)]
+mod scene_object_tests {
+    use raytracer::bounding_box::BoundingBox;
+    use raytracer::intersection::{intersect, Intersections};
+    use raytracer::material::Material;
+    use raytracer::matrix::Matrix;
+    use raytracer::ray::Ray;
+    use raytracer::scene_object::{MutSceneObject, SceneObject};
+    use raytracer::transformations::{rotate_z, scale, translate};
+    use raytracer::tuple::Tuple;
+    use std::f64::consts::{FRAC_1_SQRT_2, PI};
+    use std::sync::{Arc, Mutex};
+
+    // Shape that remembers the last ray it was intersected with and uses the object space
+    // point as its normal
+    #[derive(Debug)]
+    struct TestShape {
+        transform: Matrix,
+        material: Material,
+        saved_ray: Arc<Mutex<Option<Ray>>>,
+    }
+
+    impl SceneObject for TestShape {
+        fn id(&self) -> i32 {
+            0
+        }
+
+        fn transformation(&self) -> Matrix {
+            self.transform.clone()
+        }
+
+        fn set_transformation(&mut self, transform: &Matrix) {
+            self.transform = transform.clone();
+        }
+
+        fn material(&self) -> Material {
+            self.material.clone()
+        }
+
+        fn set_material(&mut self, material: &Material) {
+            self.material = material.clone();
+        }
+
+        fn local_intersect(&self, ray: Ray, _scene_object: MutSceneObject) -> Intersections {
+            *self.saved_ray.lock().unwrap() = Some(ray);
+            Intersections::new()
+        }
+
+        fn bounds(&self) -> BoundingBox {
+            BoundingBox::new(Tuple::point(-1.0, -1.0, -1.0), Tuple::point(1.0, 1.0, 1.0))
+        }
+
+        fn local_normal_at(&self, point: Tuple) -> Tuple {
+            Tuple::vector(point.x, point.y, point.z)
+        }
+    }
+
+    fn test_shape(transform: Matrix) -> (MutSceneObject, Arc<Mutex<Option<Ray>>>) {
+        let saved_ray = Arc::new(Mutex::new(None));
+        let shape = TestShape {
+            transform,
+            material: Material::default(),
+            saved_ray: saved_ray.clone(),
+        };
+        (Arc::new(Mutex::new(Box::new(shape))), saved_ray)
+    }
+
+    #[test]
+    fn intersect_scaled_shape() {
+        let (s, saved_ray) = test_shape(scale(2.0, 2.0, 2.0));
+        let r = Ray::new_flat(0.0, 0.0, -5.0, 0.0, 0.0, 1.0);
+
+        intersect(s, r);
+        let local = saved_ray.lock().unwrap().unwrap();
+
+        assert_eq!(local.origin, Tuple::point(0.0, 0.0, -2.5));
+        assert_eq!(local.direction, Tuple::vector(0.0, 0.0, 0.5));
+    }
+
+    #[test]
+    fn intersect_translated_shape() {
+        let (s, saved_ray) = test_shape(translate(5.0, 0.0, 0.0));
+        let r = Ray::new_flat(0.0, 0.0, -5.0, 0.0, 0.0, 1.0);
+
+        intersect(s, r);
+        let local = saved_ray.lock().unwrap().unwrap();
+
+        assert_eq!(local.origin, Tuple::point(-5.0, 0.0, -5.0));
+        assert_eq!(local.direction, Tuple::vector(0.0, 0.0, 1.0));
+    }
+
+    #[test]
+    fn normal_on_translated_shape() {
+        let (s, _) = test_shape(translate(0.0, 1.0, 0.0));
+        let n = s
+            .lock()
+            .unwrap()
+            .normal_at(Tuple::point(0.0, 1.0 + FRAC_1_SQRT_2, -FRAC_1_SQRT_2));
+
+        assert_eq!(n, Tuple::vector(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2));
+    }
+
+    #[test]
+    fn normal_on_transformed_shape() {
+        let (s, _) = test_shape(rotate_z(PI / 5.0).scale(1.0, 0.5, 1.0));
+        let sqrt = 2.0f64.sqrt() / 2.0;
+        let n = s.lock().unwrap().normal_at(Tuple::point(0.0, sqrt, -sqrt));
+
+        assert_eq!(n, Tuple::vector(0.0, 0.97014, -0.24254));
+    }
+}
@@ -0,0 +1,185 @@
+#[cfg(test)]
+mod shading_tests {
+    use raytracer::colors::Color;
+    use raytracer::light::{lighting, PointLight};
+    use raytracer::material::Material;
+    use raytracer::shading::{BlinnPhong, CookTorrance, Lambert, Phong, ShadingModel};
+    use raytracer::tuple::Tuple;
+    use std::f64::consts::FRAC_1_SQRT_2;
+    use std::sync::Arc;
+
+    fn with_model(model: impl ShadingModel + 'static) -> Material {
+        let mut m = Material::default();
+        m.shading_model = Some(Arc::new(model));
+        m
+    }
+
+    fn white() -> Color {
+        Color::new(1.0, 1.0, 1.0)
+    }
+
+    // Lights the origin of a surface facing -z, seen from `eye`
+    fn light_surface(m: &Material, light_position: Tuple, eye: Tuple) -> Color {
+        let position = Tuple::point(0.0, 0.0, 0.0);
+        let normal = Tuple::vector(0.0, 0.0, -1.0);
+        let light = PointLight::new(light_position, white());
+        lighting(m, &light, position, position, eye, normal, white())
+    }
+
+    #[test]
+    fn materials_use_phong_by_default() {
+        let eyes = [
+            Tuple::vector(0.0, 0.0, -1.0),
+            Tuple::vector(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+            Tuple::vector(0.0, -FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+        ];
+        let lights = [
+            Tuple::point(0.0, 0.0, -10.0),
+            Tuple::point(0.0, 10.0, -10.0),
+        ];
+
+        for eye in eyes {
+            for light in lights {
+                assert_eq!(
+                    light_surface(&Material::default(), light, eye),
+                    light_surface(&with_model(Phong), light, eye)
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn blinn_phong_highlight_from_half_vector() {
+        let mut phong = with_model(Phong);
+        phong.shininess = 10.0;
+        let mut blinn = with_model(BlinnPhong);
+        blinn.shininess = 10.0;
+        let light = Tuple::point(0.0, 0.0, -10.0);
+        let eye = Tuple::vector(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2);
+
+        // The half-vector is only 22.5° off the normal, while the reflection is 45° off the eye
+        assert_eq!(
+            light_surface(&phong, light, eye),
+            Color::new(1.02813, 1.02813, 1.02813)
+        );
+        assert_eq!(
+            light_surface(&blinn, light, eye),
+            Color::new(1.40775, 1.40775, 1.40775)
+        );
+    }
+
+    #[test]
+    fn blinn_phong_matches_phong_on_reflection_vector() {
+        let light = Tuple::point(0.0, 10.0, -10.0);
+        let eye = Tuple::vector(0.0, -FRAC_1_SQRT_2, -FRAC_1_SQRT_2);
+
+        assert_eq!(
+            light_surface(&with_model(BlinnPhong), light, eye),
+            Color::new(1.6364, 1.6364, 1.6364)
+        );
+    }
+
+    #[test]
+    fn lambert_has_no_highlight() {
+        let light = Tuple::point(0.0, 10.0, -10.0);
+        let eye = Tuple::vector(0.0, -FRAC_1_SQRT_2, -FRAC_1_SQRT_2);
+
+        assert_eq!(
+            light_surface(&with_model(Lambert), light, eye),
+            Color::new(0.7364, 0.7364, 0.7364)
+        );
+    }
+
+    #[test]
+    fn light_behind_surface_leaves_ambient_with_every_model() {
+        let models: Vec<Arc<dyn ShadingModel>> = vec![
+            Arc::new(Phong),
+            Arc::new(BlinnPhong),
+            Arc::new(Lambert),
+            Arc::new(CookTorrance::new(0.0, 0.5)),
+            Arc::new(CookTorrance::new(1.0, 0.2)),
+        ];
+        for model in models {
+            let mut m = Material::default();
+            m.shading_model = Some(model);
+
+            assert_eq!(
+                light_surface(
+                    &m,
+                    Tuple::point(0.0, 0.0, 10.0),
+                    Tuple::vector(0.0, 0.0, -1.0)
+                ),
+                Color::new(0.1, 0.1, 0.1)
+            );
+        }
+    }
+
+    #[test]
+    fn rough_dielectric_facing_light_is_about_white() {
+        let m = with_model(CookTorrance::new(0.0, 1.0));
+        let c = light_surface(
+            &m,
+            Tuple::point(0.0, 0.0, -10.0),
+            Tuple::vector(0.0, 0.0, -1.0),
+        );
+
+        // 96% diffuse, 1% specular, plus the material's ambient
+        assert_eq!(c, Color::new(1.07, 1.07, 1.07));
+    }
+
+    #[test]
+    fn metal_highlights_take_on_base_color() {
+        let mut m = with_model(CookTorrance::new(1.0, 0.3));
+        m.color = Color::new(1.0, 0.5, 0.0);
+        let c = light_surface(
+            &m,
+            Tuple::point(0.0, 0.0, -10.0),
+            Tuple::vector(0.0, 0.0, -1.0),
+        );
+
+        assert_eq!(c, Color::new(30.9642, 15.48210, 0.0));
+    }
+
+    #[test]
+    fn metal_has_no_diffuse_light() {
+        let m = with_model(CookTorrance::new(1.0, 0.1));
+        let c = light_surface(
+            &m,
+            Tuple::point(0.0, 0.0, -10.0),
+            Tuple::vector(0.0, FRAC_1_SQRT_2, -FRAC_1_SQRT_2),
+        );
+
+        // Far from the mirror direction a smooth metal is almost only ambient
+        assert!(c.red() < 0.11);
+    }
+
+    #[test]
+    fn smoother_surfaces_have_sharper_highlights() {
+        let smooth = with_model(CookTorrance::new(0.0, 0.2));
+        let rough = with_model(CookTorrance::new(0.0, 0.8));
+        let light = Tuple::point(0.0, 10.0, -10.0);
+        let mirror = Tuple::vector(0.0, -FRAC_1_SQRT_2, -FRAC_1_SQRT_2);
+        let off_mirror = Tuple::vector(0.0, -0.2, -1.0).normalize();
+
+        assert!(
+            light_surface(&smooth, light, mirror).red()
+                > light_surface(&rough, light, mirror).red()
+        );
+        assert!(
+            light_surface(&smooth, light, off_mirror).red()
+                < light_surface(&rough, light, off_mirror).red()
+        );
+    }
+
+    #[test]
+    fn materials_are_equal_when_sharing_a_model() {
+        let model: Arc<dyn ShadingModel> = Arc::new(Lambert);
+        let mut a = Material::default();
+        a.shading_model = Some(model.clone());
+        let mut b = Material::default();
+        b.shading_model = Some(model);
+
+        assert_eq!(a, b);
+        assert_ne!(a, Material::default());
+    }
+}
@@ -1,42 +1,90 @@
 #[cfg(test)]
 mod shadow_tests {
     use raytracer::colors::Color;
-    use raytracer::intersection::{is_shadowed, prepare_computations, shade_hit, Intersection};
-    use raytracer::light::PointLight;
+    use raytracer::group::group;
+    use raytracer::intersection::{
+        is_shadowed, prepare_computations, shade_hit, Intersection, Intersections,
+    };
+    use raytracer::light::{AreaLight, DirectionalLight, Light, PointLight, SphereLight};
+    use raytracer::material::Material;
     use raytracer::ray::Ray;
-    use raytracer::sphere::{sphere, Sphere};
+    use raytracer::scene_object::MutSceneObject;
+    use raytracer::sphere::{glass_sphere, sphere, Sphere};
     use raytracer::transformations::translate;
     use raytracer::tuple::Tuple;
     use raytracer::utils::EPSILON;
-    use raytracer::world::World;
+    use raytracer::world::{World, DEFAULT_MAX_DEPTH};
     use std::sync::Arc;
 
+    // Light transmittance that lets through the same fraction of every channel
+    fn visible(fraction: f64) -> Color {
+        Color::new(fraction, fraction, fraction)
+    }
+
+    // World with a light straight above the given objects
+    fn world_lit_from_above(objects: Vec<MutSceneObject>) -> Arc<World> {
+        let mut w = World::new();
+        w.light_sources.push(Box::new(PointLight::new(
+            Tuple::point(0.0, 10.0, 0.0),
+            Color::new(1.0, 1.0, 1.0),
+        )));
+        w.objects = objects;
+        Arc::new(w)
+    }
+
     #[test]
     pub fn no_shadow_when_nothing_is_collinear_with_point_and_light() {
-        let w = World::default_world();
+        let w = Arc::new(World::default_world());
         let p = Tuple::point(0.0, 10.0, 0.0);
-        assert!(!is_shadowed(Arc::new(w), p));
+        assert_eq!(
+            is_shadowed(w.clone(), w.light_sources[0].as_ref(), p),
+            visible(1.0)
+        );
     }
 
     #[test]
     pub fn shadow_when_object_between_point_and_light() {
-        let w = World::default_world();
+        let w = Arc::new(World::default_world());
         let p = Tuple::point(10.0, -10.0, 10.0);
-        assert!(is_shadowed(Arc::new(w), p));
+        assert_eq!(
+            is_shadowed(w.clone(), w.light_sources[0].as_ref(), p),
+            visible(0.0)
+        );
     }
 
     #[test]
     pub fn no_shadow_when_object_behind_light() {
-        let w = World::default_world();
+        let w = Arc::new(World::default_world());
         let p = Tuple::point(-20.0, 20.0, -20.0);
-        assert!(!is_shadowed(Arc::new(w), p));
+        assert_eq!(
+            is_shadowed(w.clone(), w.light_sources[0].as_ref(), p),
+            visible(1.0)
+        );
     }
 
     #[test]
     pub fn no_shadow_whe_object_behind_point() {
-        let w = World::default_world();
+        let w = Arc::new(World::default_world());
         let p = Tuple::point(-2.0, 2.0, -2.0);
-        assert!(!is_shadowed(Arc::new(w), p));
+        assert_eq!(
+            is_shadowed(w.clone(), w.light_sources[0].as_ref(), p),
+            visible(1.0)
+        );
+    }
+
+    #[test]
+    pub fn directional_light_shadows_reach_any_distance() {
+        let w = Arc::new(World::default_world());
+        let sun = DirectionalLight::new(Tuple::vector(1.0, -1.0, 1.0), Color::new(1.0, 1.0, 1.0));
+
+        let near = Tuple::point(10.0, -10.0, 10.0);
+        let far = Tuple::point(1000.0, -1000.0, 1000.0);
+        assert_eq!(is_shadowed(w.clone(), &sun, near), visible(0.0));
+        assert_eq!(is_shadowed(w.clone(), &sun, far), visible(0.0));
+        assert_eq!(
+            is_shadowed(w.clone(), &sun, Tuple::point(-2.0, 2.0, -2.0)),
+            visible(1.0)
+        );
     }
 
     #[test]
@@ -55,13 +103,13 @@ mod shadow_tests {
             .set_transformation(&sphere2_transformation);
         world.objects.push(sphere2.clone());
 
-        world.light_sources.push(light);
+        world.light_sources.push(Box::new(light));
 
         let r = Ray::new(Tuple::point(0.0, 0.0, 5.0), Tuple::vector(0.0, 0.0, 1.0));
-        let i = Intersection::new(4.0, sphere2);
+        let i = Arc::new(Intersection::new(4.0, sphere2));
 
-        let comps = prepare_computations(Arc::new(i), r);
-        let c = shade_hit(Arc::new(world), &comps);
+        let comps = prepare_computations(i.clone(), r, &Intersections::from(vec![i]));
+        let c = shade_hit(Arc::new(world), &comps, DEFAULT_MAX_DEPTH);
 
         let expected_color = Color::new(0.1, 0.1, 0.1);
         assert_eq!(c, expected_color);
@@ -75,10 +123,243 @@ mod shadow_tests {
         let s_transform = translate(0.0, 0.0, 1.0);
         s.lock().unwrap().set_transformation(&s_transform);
 
-        let i = Intersection::new(5.0, s);
-        let comps = prepare_computations(Arc::new(i), r);
+        let i = Arc::new(Intersection::new(5.0, s));
+        let comps = prepare_computations(i.clone(), r, &Intersections::from(vec![i]));
 
         assert!(comps.over_point.z < -EPSILON / 2.0);
         assert!(comps.point.z > comps.over_point.z);
     }
+
+    #[test]
+    pub fn area_light_samples_cover_its_surface() {
+        let light = AreaLight::new(
+            Tuple::point(0.0, 0.0, 0.0),
+            Tuple::vector(2.0, 0.0, 0.0),
+            4,
+            Tuple::vector(0.0, 0.0, 1.0),
+            2,
+            Color::new(1.0, 1.0, 1.0),
+        );
+
+        assert_eq!(light.center(), Tuple::point(1.0, 0.0, 0.5));
+        assert_eq!(light.point_on_light(0, 0), Tuple::point(0.25, 0.0, 0.25));
+        assert_eq!(light.point_on_light(1, 0), Tuple::point(0.75, 0.0, 0.25));
+        assert_eq!(light.point_on_light(3, 1), Tuple::point(1.75, 0.0, 0.75));
+        assert_eq!(light.samples_from(Tuple::point(0.0, 5.0, 0.0)).len(), 8);
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn area_light_needs_samples_along_both_sides() {
+        AreaLight::new(
+            Tuple::point(0.0, 0.0, 0.0),
+            Tuple::vector(2.0, 0.0, 0.0),
+            4,
+            Tuple::vector(0.0, 0.0, 1.0),
+            0,
+            Color::new(1.0, 1.0, 1.0),
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    pub fn sphere_light_needs_samples() {
+        SphereLight::new(
+            Tuple::point(0.0, 3.0, 0.0),
+            1.0,
+            0,
+            Color::new(1.0, 1.0, 1.0),
+        );
+    }
+
+    #[test]
+    pub fn light_without_samples_is_tested_at_its_center() {
+        let mut light = SphereLight::new(
+            Tuple::point(0.0, 10.0, 0.0),
+            1.0,
+            4,
+            Color::new(1.0, 1.0, 1.0),
+        );
+        light.samples = 0;
+        let w = world_lit_from_above(vec![sphere()]);
+
+        assert_eq!(
+            is_shadowed(w.clone(), &light, Tuple::point(0.0, -5.0, 0.0)),
+            visible(0.0)
+        );
+        assert_eq!(
+            is_shadowed(w, &light, Tuple::point(5.0, -5.0, 0.0)),
+            visible(1.0)
+        );
+    }
+
+    #[test]
+    pub fn area_light_gives_partial_shadow() {
+        // 2x2 light spanning x from -1 to 1 right above the default world's spheres
+        let mut w = World::default_world();
+        w.light_sources[0] = Box::new(AreaLight::new(
+            Tuple::point(-1.0, 3.0, -1.0),
+            Tuple::vector(2.0, 0.0, 0.0),
+            2,
+            Tuple::vector(0.0, 0.0, 2.0),
+            2,
+            Color::new(1.0, 1.0, 1.0),
+        ));
+        let w = Arc::new(w);
+        let light = w.light_sources[0].as_ref();
+
+        // Off to the side nothing's in the way, right below the sphere all of it is blocked,
+        // and near the edge of the shadow only the far half of the light is hidden
+        assert_eq!(
+            is_shadowed(w.clone(), light, Tuple::point(5.0, -1.0, 0.0)),
+            visible(1.0)
+        );
+        assert_eq!(
+            is_shadowed(w.clone(), light, Tuple::point(0.0, -1.0, 0.0)),
+            visible(0.0)
+        );
+        assert_eq!(
+            is_shadowed(w.clone(), light, Tuple::point(1.6, -1.0, 0.0)),
+            visible(0.5)
+        );
+    }
+
+    #[test]
+    pub fn sphere_light_gives_partial_shadow() {
+        let mut w = World::default_world();
+        w.light_sources[0] = Box::new(SphereLight::new(
+            Tuple::point(0.0, 3.0, 0.0),
+            1.0,
+            16,
+            Color::new(1.0, 1.0, 1.0),
+        ));
+        let w = Arc::new(w);
+        let light = w.light_sources[0].as_ref();
+
+        let visibility = is_shadowed(w.clone(), light, Tuple::point(1.3, -1.0, 0.0)).red();
+        assert!(visibility > 0.0 && visibility < 1.0);
+        assert_eq!(
+            is_shadowed(w.clone(), light, Tuple::point(0.0, -1.0, 0.0)),
+            visible(0.0)
+        );
+    }
+
+    #[test]
+    pub fn glass_casts_no_shadow() {
+        let w = world_lit_from_above(vec![glass_sphere()]);
+
+        assert_eq!(
+            is_shadowed(
+                w.clone(),
+                w.light_sources[0].as_ref(),
+                Tuple::point(0.0, -5.0, 0.0)
+            ),
+            visible(1.0)
+        );
+    }
+
+    #[test]
+    pub fn tinted_glass_casts_colored_shadow() {
+        let s = glass_sphere();
+        let mut m = s.lock().unwrap().material();
+        m.color = Color::new(1.0, 0.5, 0.0);
+        m.transparency = 0.5;
+        s.lock().unwrap().set_material(&m);
+        let w = world_lit_from_above(vec![s]);
+
+        // Entering and leaving the sphere only tints the light once
+        assert_eq!(
+            is_shadowed(
+                w.clone(),
+                w.light_sources[0].as_ref(),
+                Tuple::point(0.0, -5.0, 0.0)
+            ),
+            Color::new(0.5, 0.25, 0.0)
+        );
+    }
+
+    #[test]
+    pub fn transmittance_multiplies_through_every_object() {
+        let a = glass_sphere();
+        let b = glass_sphere();
+        b.lock()
+            .unwrap()
+            .set_transformation(&translate(0.0, 3.0, 0.0));
+        for s in [&a, &b] {
+            let mut m = s.lock().unwrap().material();
+            m.transparency = 0.5;
+            s.lock().unwrap().set_material(&m);
+        }
+        let w = world_lit_from_above(vec![a, b]);
+
+        assert_eq!(
+            is_shadowed(
+                w.clone(),
+                w.light_sources[0].as_ref(),
+                Tuple::point(0.0, -5.0, 0.0)
+            ),
+            visible(0.25)
+        );
+    }
+
+    #[test]
+    pub fn shared_glass_counts_once_per_instance() {
+        let glass = glass_sphere();
+        let mut m = glass.lock().unwrap().material();
+        m.transparency = 0.5;
+        glass.lock().unwrap().set_material(&m);
+        let a = group(vec![glass.clone()]);
+        let b = group(vec![glass]);
+        b.lock()
+            .unwrap()
+            .set_transformation(&translate(0.0, 3.0, 0.0));
+        let w = world_lit_from_above(vec![a, b]);
+
+        assert_eq!(
+            is_shadowed(
+                w.clone(),
+                w.light_sources[0].as_ref(),
+                Tuple::point(0.0, -5.0, 0.0)
+            ),
+            visible(0.25)
+        );
+    }
+
+    #[test]
+    pub fn opaque_object_behind_glass_still_blocks() {
+        let blocker = sphere();
+        blocker
+            .lock()
+            .unwrap()
+            .set_transformation(&translate(0.0, 3.0, 0.0));
+        let w = world_lit_from_above(vec![glass_sphere(), blocker]);
+
+        assert_eq!(
+            is_shadowed(
+                w.clone(),
+                w.light_sources[0].as_ref(),
+                Tuple::point(0.0, -5.0, 0.0)
+            ),
+            visible(0.0)
+        );
+    }
+
+    #[test]
+    pub fn objects_can_opt_out_of_casting_shadows() {
+        let s = sphere();
+        let mut m = Material::default();
+        m.casts_shadow = false;
+        s.lock().unwrap().set_material(&m);
+        let w = world_lit_from_above(vec![s]);
+
+        assert!(Material::default().casts_shadow);
+        assert_eq!(
+            is_shadowed(
+                w.clone(),
+                w.light_sources[0].as_ref(),
+                Tuple::point(0.0, -5.0, 0.0)
+            ),
+            visible(1.0)
+        );
+    }
 }
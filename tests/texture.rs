@@ -0,0 +1,221 @@
+#[cfg(test)]
+mod texture_tests {
+    use raytracer::canvas::Canvas;
+    use raytracer::colors::Color;
+    use raytracer::material::Material;
+    use raytracer::pattern::Pattern;
+    use raytracer::texture::{
+        face_from_point, CubeFace, TextureMapPattern, UvCheckers, UvImage, UvMapping, UvPattern,
+    };
+    use raytracer::tuple::Tuple;
+    use raytracer::utils::f64_eq;
+    use std::f64::consts::FRAC_1_SQRT_2;
+    use std::sync::Arc;
+
+    fn white() -> Color {
+        Color::new(1.0, 1.0, 1.0)
+    }
+
+    fn black() -> Color {
+        Color::new(0.0, 0.0, 0.0)
+    }
+
+    fn assert_maps_to(mapping: UvMapping, point: Tuple, u: f64, v: f64) {
+        let (actual_u, actual_v) = mapping.map(point);
+        assert!(
+            f64_eq(actual_u, u) && f64_eq(actual_v, v),
+            "{:?} maps {:?} to ({}, {}), expected ({}, {})",
+            mapping,
+            point,
+            actual_u,
+            actual_v,
+            u,
+            v
+        );
+    }
+
+    #[test]
+    fn uv_checkers() {
+        let p = UvCheckers::new(2.0, 2.0, black(), white());
+
+        assert_eq!(p.uv_pattern_at(0.0, 0.0), black());
+        assert_eq!(p.uv_pattern_at(0.5, 0.0), white());
+        assert_eq!(p.uv_pattern_at(0.0, 0.5), white());
+        assert_eq!(p.uv_pattern_at(0.5, 0.5), black());
+        assert_eq!(p.uv_pattern_at(1.0, 1.0), black());
+    }
+
+    #[test]
+    fn spherical_mapping() {
+        let m = UvMapping::Spherical;
+
+        assert_maps_to(m, Tuple::point(0.0, 0.0, -1.0), 0.0, 0.5);
+        assert_maps_to(m, Tuple::point(1.0, 0.0, 0.0), 0.25, 0.5);
+        assert_maps_to(m, Tuple::point(0.0, 0.0, 1.0), 0.5, 0.5);
+        assert_maps_to(m, Tuple::point(-1.0, 0.0, 0.0), 0.75, 0.5);
+        assert_maps_to(m, Tuple::point(0.0, 1.0, 0.0), 0.5, 1.0);
+        assert_maps_to(m, Tuple::point(0.0, -1.0, 0.0), 0.5, 0.0);
+        assert_maps_to(
+            m,
+            Tuple::point(FRAC_1_SQRT_2, FRAC_1_SQRT_2, 0.0),
+            0.25,
+            0.75,
+        );
+    }
+
+    #[test]
+    fn texture_map_with_spherical_mapping() {
+        let checkers = Arc::new(UvCheckers::new(16.0, 8.0, black(), white()));
+        let p = TextureMapPattern::new(checkers, UvMapping::Spherical);
+
+        for (x, y, z, expected) in [
+            (0.4315, 0.4670, 0.7719, white()),
+            (-0.9654, 0.2552, -0.0534, black()),
+            (0.1039, 0.7090, 0.6975, white()),
+            (-0.4986, -0.7856, -0.3663, black()),
+            (-0.0317, -0.9395, 0.3411, black()),
+            (0.4809, -0.7721, 0.4154, black()),
+            (0.0285, -0.9612, -0.2745, black()),
+            (-0.5734, -0.2162, -0.7903, white()),
+            (0.7688, -0.1470, 0.6223, black()),
+            (-0.7652, 0.2175, 0.6060, black()),
+        ] {
+            assert_eq!(p.pattern_at(Tuple::point(x, y, z)), expected);
+        }
+    }
+
+    #[test]
+    fn planar_mapping() {
+        let m = UvMapping::Planar;
+
+        assert_maps_to(m, Tuple::point(0.25, 0.0, 0.5), 0.25, 0.5);
+        assert_maps_to(m, Tuple::point(0.25, 0.0, -0.25), 0.25, 0.75);
+        assert_maps_to(m, Tuple::point(0.25, 0.5, -0.25), 0.25, 0.75);
+        assert_maps_to(m, Tuple::point(1.25, 0.0, 0.5), 0.25, 0.5);
+        assert_maps_to(m, Tuple::point(0.25, 0.0, -1.75), 0.25, 0.25);
+        assert_maps_to(m, Tuple::point(1.0, 0.0, -1.0), 0.0, 0.0);
+        assert_maps_to(m, Tuple::point(0.0, 0.0, 0.0), 0.0, 0.0);
+    }
+
+    #[test]
+    fn cylindrical_mapping() {
+        let m = UvMapping::Cylindrical;
+        let d = FRAC_1_SQRT_2;
+
+        assert_maps_to(m, Tuple::point(0.0, 0.0, -1.0), 0.0, 0.0);
+        assert_maps_to(m, Tuple::point(0.0, 0.5, -1.0), 0.0, 0.5);
+        assert_maps_to(m, Tuple::point(0.0, 1.0, -1.0), 0.0, 0.0);
+        assert_maps_to(m, Tuple::point(d, 0.5, -d), 0.125, 0.5);
+        assert_maps_to(m, Tuple::point(1.0, 0.5, 0.0), 0.25, 0.5);
+        assert_maps_to(m, Tuple::point(d, 0.5, d), 0.375, 0.5);
+        assert_maps_to(m, Tuple::point(0.0, -0.25, 1.0), 0.5, 0.75);
+        assert_maps_to(m, Tuple::point(-d, 0.5, d), 0.625, 0.5);
+        assert_maps_to(m, Tuple::point(-1.0, 1.25, 0.0), 0.75, 0.25);
+        assert_maps_to(m, Tuple::point(-d, 0.5, -d), 0.875, 0.5);
+    }
+
+    #[test]
+    fn cube_face_from_point() {
+        assert_eq!(
+            face_from_point(Tuple::point(-1.0, 0.5, -0.25)),
+            CubeFace::Left
+        );
+        assert_eq!(
+            face_from_point(Tuple::point(1.1, -0.75, 0.8)),
+            CubeFace::Right
+        );
+        assert_eq!(
+            face_from_point(Tuple::point(0.1, 0.6, 0.9)),
+            CubeFace::Front
+        );
+        assert_eq!(
+            face_from_point(Tuple::point(-0.7, 0.0, -2.0)),
+            CubeFace::Back
+        );
+        assert_eq!(face_from_point(Tuple::point(0.5, 1.0, 0.9)), CubeFace::Up);
+        assert_eq!(
+            face_from_point(Tuple::point(-0.2, -1.3, 1.1)),
+            CubeFace::Down
+        );
+    }
+
+    #[test]
+    fn cube_mapping() {
+        let m = UvMapping::Cube;
+
+        // Front, then the upper left corner of every face
+        assert_maps_to(m, Tuple::point(-0.5, 0.5, 1.0), 0.25, 0.75);
+        assert_maps_to(m, Tuple::point(0.5, -0.5, 1.0), 0.75, 0.25);
+        assert_maps_to(m, Tuple::point(0.5, 0.5, -1.0), 0.25, 0.75);
+        assert_maps_to(m, Tuple::point(-1.0, 0.5, -0.5), 0.25, 0.75);
+        assert_maps_to(m, Tuple::point(1.0, 0.5, 0.5), 0.25, 0.75);
+        assert_maps_to(m, Tuple::point(-0.5, 1.0, -0.5), 0.25, 0.75);
+        assert_maps_to(m, Tuple::point(-0.5, -1.0, 0.5), 0.25, 0.75);
+    }
+
+    #[test]
+    fn image_corners_and_bilinear_sampling() {
+        let mut canvas = Canvas::new(2, 2);
+        canvas.write_pixel(0, 0, white());
+        canvas.write_pixel(1, 1, Color::new(0.0, 0.0, 1.0));
+        let image = UvImage::new(canvas);
+
+        // v = 1 is the top row of the image
+        assert_eq!(image.uv_pattern_at(0.0, 1.0), white());
+        assert_eq!(image.uv_pattern_at(1.0, 1.0), black());
+        assert_eq!(image.uv_pattern_at(1.0, 0.0), Color::new(0.0, 0.0, 1.0));
+        assert_eq!(image.uv_pattern_at(0.5, 1.0), Color::new(0.5, 0.5, 0.5));
+        assert_eq!(image.uv_pattern_at(0.5, 0.5), Color::new(0.25, 0.25, 0.5));
+    }
+
+    #[test]
+    fn image_read_from_png() {
+        let mut canvas = Canvas::new(3, 2);
+        canvas.write_pixel(0, 0, Color::new(1.0, 0.0, 0.0));
+        canvas.write_pixel(2, 1, Color::new(0.2, 0.4, 0.6));
+        let path = std::env::temp_dir().join("raytracer_texture_test.png");
+        let path = path.to_str().unwrap();
+        canvas.to_png_file(path);
+
+        let image = UvImage::from_png_file(path).unwrap();
+        assert_eq!(image.canvas.width(), 3);
+        assert_eq!(image.canvas.height(), 2);
+        assert_eq!(image.uv_pattern_at(0.0, 1.0), Color::new(1.0, 0.0, 0.0));
+        assert_eq!(image.uv_pattern_at(1.0, 0.0), Color::new(0.2, 0.4, 0.6));
+        assert_eq!(image.uv_pattern_at(0.5, 1.0), black());
+    }
+
+    #[test]
+    fn grayscale_image_read_from_png() {
+        let path = std::env::temp_dir().join("raytracer_texture_gray_test.png");
+        let file = std::fs::File::create(&path).unwrap();
+        let mut encoder = png::Encoder::new(std::io::BufWriter::new(file), 2, 1);
+        encoder.set_color(png::ColorType::Grayscale);
+        encoder.set_depth(png::BitDepth::Eight);
+        let mut writer = encoder.write_header().unwrap();
+        writer.write_image_data(&[51, 255]).unwrap();
+        writer.finish().unwrap();
+
+        let image = UvImage::from_png_file(path.to_str().unwrap()).unwrap();
+        assert_eq!(image.uv_pattern_at(0.0, 0.0), Color::new(0.2, 0.2, 0.2));
+        assert_eq!(image.uv_pattern_at(1.0, 0.0), white());
+    }
+
+    #[test]
+    fn reading_missing_png_fails() {
+        assert!(UvImage::from_png_file("/nonexistent/texture.png").is_err());
+    }
+
+    #[test]
+    fn texture_as_material_color_source() {
+        let checkers = Arc::new(UvCheckers::new(2.0, 2.0, black(), white()));
+        let mut m = Material::default();
+        m.pattern = Some(Arc::new(TextureMapPattern::new(
+            checkers,
+            UvMapping::Planar,
+        )));
+
+        assert_eq!(m.color_at(Tuple::point(0.25, 0.0, 0.25)), black());
+        assert_eq!(m.color_at(Tuple::point(0.75, 0.0, 0.25)), white());
+    }
+}
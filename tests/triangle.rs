@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod triangle_tests {
+    use raytracer::intersection::{prepare_computations, Intersection, Intersections};
+    use raytracer::ray::Ray;
+    use raytracer::scene_object::MutSceneObject;
+    use raytracer::triangle::{smooth_triangle, triangle, Triangle};
+    use raytracer::tuple::Tuple;
+    use raytracer::utils::f64_eq;
+    use std::sync::Arc;
+
+    fn default_triangle() -> MutSceneObject {
+        triangle(
+            Tuple::point(0.0, 1.0, 0.0),
+            Tuple::point(-1.0, 0.0, 0.0),
+            Tuple::point(1.0, 0.0, 0.0),
+        )
+    }
+
+    fn default_smooth_triangle() -> MutSceneObject {
+        smooth_triangle(
+            Tuple::point(0.0, 1.0, 0.0),
+            Tuple::point(-1.0, 0.0, 0.0),
+            Tuple::point(1.0, 0.0, 0.0),
+            Tuple::vector(0.0, 1.0, 0.0),
+            Tuple::vector(-1.0, 0.0, 0.0),
+            Tuple::vector(1.0, 0.0, 0.0),
+        )
+    }
+
+    #[test]
+    fn triangle_construction() {
+        let t = Triangle::new(
+            Tuple::point(0.0, 1.0, 0.0),
+            Tuple::point(-1.0, 0.0, 0.0),
+            Tuple::point(1.0, 0.0, 0.0),
+        );
+
+        assert_eq!(t.e1, Tuple::vector(-1.0, -1.0, 0.0));
+        assert_eq!(t.e2, Tuple::vector(1.0, -1.0, 0.0));
+        assert_eq!(t.normal, Tuple::vector(0.0, 0.0, -1.0));
+    }
+
+    #[test]
+    fn triangle_normal_is_constant() {
+        let t = default_triangle();
+        let guard = t.lock().unwrap();
+        let expected = Tuple::vector(0.0, 0.0, -1.0);
+
+        assert_eq!(guard.local_normal_at(Tuple::point(0.0, 0.5, 0.0)), expected);
+        assert_eq!(
+            guard.local_normal_at(Tuple::point(-0.5, 0.75, 0.0)),
+            expected
+        );
+        assert_eq!(
+            guard.local_normal_at(Tuple::point(0.5, 0.25, 0.0)),
+            expected
+        );
+    }
+
+    #[test]
+    fn ray_misses_triangle() {
+        let t = default_triangle();
+        let cases = [
+            // parallel, past p1-p3 edge, past p1-p2 edge, past p2-p3 edge
+            (Tuple::point(0.0, -1.0, -2.0), Tuple::vector(0.0, 1.0, 0.0)),
+            (Tuple::point(1.0, 1.0, -2.0), Tuple::vector(0.0, 0.0, 1.0)),
+            (Tuple::point(-1.0, 1.0, -2.0), Tuple::vector(0.0, 0.0, 1.0)),
+            (Tuple::point(0.0, -1.0, -2.0), Tuple::vector(0.0, 0.0, 1.0)),
+        ];
+
+        for (origin, direction) in cases {
+            let r = Ray::new(origin, direction);
+            let xs = t.lock().unwrap().local_intersect(r, t.clone());
+            assert_eq!(xs.len(), 0);
+        }
+    }
+
+    #[test]
+    fn ray_strikes_triangle() {
+        let t = default_triangle();
+        let r = Ray::new_flat(0.0, 0.5, -2.0, 0.0, 0.0, 1.0);
+
+        let xs = t.lock().unwrap().local_intersect(r, t.clone());
+        assert_eq!(xs.len(), 1);
+        assert_eq!(xs[0].t, 2.0);
+    }
+
+    #[test]
+    fn intersection_stores_uv() {
+        let t = default_smooth_triangle();
+        let r = Ray::new_flat(-0.2, 0.3, -2.0, 0.0, 0.0, 1.0);
+
+        let xs = t.lock().unwrap().local_intersect(r, t.clone());
+        assert!(f64_eq(xs[0].u, 0.45));
+        assert!(f64_eq(xs[0].v, 0.25));
+    }
+
+    #[test]
+    fn smooth_triangle_interpolates_normal() {
+        let t = default_smooth_triangle();
+        let i = Intersection::new_with_uv(1.0, t.clone(), 0.45, 0.25);
+
+        let n = t
+            .lock()
+            .unwrap()
+            .normal_at_hit(Tuple::point(0.0, 0.0, 0.0), &i);
+        assert_eq!(n, Tuple::vector(-0.5547, 0.83205, 0.0));
+    }
+
+    #[test]
+    fn smooth_triangle_precomputed_normal() {
+        let t = default_smooth_triangle();
+        let i = Arc::new(Intersection::new_with_uv(1.0, t.clone(), 0.45, 0.25));
+        let r = Ray::new_flat(-0.2, 0.3, -2.0, 0.0, 0.0, 1.0);
+
+        let comps = prepare_computations(i.clone(), r, &Intersections::from(vec![i]));
+        assert_eq!(comps.normal_vector, Tuple::vector(-0.5547, 0.83205, 0.0));
+    }
+}
@@ -2,13 +2,14 @@
 mod world_tests {
     use raytracer::colors::Color;
     use raytracer::intersection::{
-        color_at, intersect_world, prepare_computations, shade_hit, Intersection,
+        color_at, intersect_world, prepare_computations, shade_hit, Intersection, Intersections,
     };
     use raytracer::light::PointLight;
     use raytracer::ray::Ray;
     use raytracer::sphere::sphere;
+    use raytracer::transformations::scale;
     use raytracer::tuple::Tuple;
-    use raytracer::world::World;
+    use raytracer::world::{World, DEFAULT_MAX_DEPTH};
     use std::sync::Arc;
 
     #[test]
@@ -45,7 +46,7 @@ mod world_tests {
         let shape = sphere();
         let i = Arc::new(Intersection::new(4.0, shape.clone()));
 
-        let actual = prepare_computations(i, r);
+        let actual = prepare_computations(i.clone(), r, &Intersections::from(vec![i]));
 
         let shape_id = shape.lock().unwrap().id();
 
@@ -61,7 +62,7 @@ mod world_tests {
         let shape = sphere();
         let i = Arc::new(Intersection::new(4.0, shape.clone()));
 
-        let actual = prepare_computations(i, r);
+        let actual = prepare_computations(i.clone(), r, &Intersections::from(vec![i]));
         assert_eq!(actual.is_inside_object, false);
     }
 
@@ -71,7 +72,7 @@ mod world_tests {
         let shape = sphere();
         let i = Arc::new(Intersection::new(1.0, shape.clone()));
 
-        let actual = prepare_computations(i, r);
+        let actual = prepare_computations(i.clone(), r, &Intersections::from(vec![i]));
 
         assert_eq!(actual.is_inside_object, true);
         assert_eq!(actual.point, Tuple::point(0.0, 0.0, 1.0));
@@ -87,8 +88,8 @@ mod world_tests {
         let shape = w.objects[0].clone();
         let i = Arc::new(Intersection::new(4.0, shape.clone()));
 
-        let comps = prepare_computations(i, ray);
-        let color = shade_hit(Arc::new(w), &comps);
+        let comps = prepare_computations(i.clone(), ray, &Intersections::from(vec![i]));
+        let color = shade_hit(Arc::new(w), &comps, DEFAULT_MAX_DEPTH);
 
         assert_eq!(color, Color::new(0.38066, 0.47583, 0.2855));
     }
@@ -96,14 +97,16 @@ mod world_tests {
     #[test]
     fn test_intersection_shading_inside() {
         let mut w = World::default_world();
-        w.light_sources[0] =
-            PointLight::new(Tuple::point(0.0, 0.25, 0.0), Color::new(1.0, 1.0, 1.0));
+        w.light_sources[0] = Box::new(PointLight::new(
+            Tuple::point(0.0, 0.25, 0.0),
+            Color::new(1.0, 1.0, 1.0),
+        ));
         let ray = Ray::new(Tuple::point(0.0, 0.0, 0.0), Tuple::vector(0.0, 0.0, 1.0));
         let shape = w.objects[1].clone();
         let i = Arc::new(Intersection::new(0.5, shape.clone()));
 
-        let comps = prepare_computations(i, ray);
-        let color = shade_hit(Arc::new(w), &comps);
+        let comps = prepare_computations(i.clone(), ray, &Intersections::from(vec![i]));
+        let color = shade_hit(Arc::new(w), &comps, DEFAULT_MAX_DEPTH);
 
         assert_eq!(color, Color::new(0.90498, 0.90498, 0.90498));
     }
@@ -113,7 +116,7 @@ mod world_tests {
         let w = World::default_world();
         let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 1.0, 0.0));
 
-        let actual = color_at(Arc::new(w), r);
+        let actual = color_at(Arc::new(w), r, DEFAULT_MAX_DEPTH);
         let expected = Color::new(0.0, 0.0, 0.0);
 
         assert_eq!(actual, expected);
@@ -124,7 +127,7 @@ mod world_tests {
         let w = World::default_world();
         let r = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
 
-        let actual = color_at(Arc::new(w), r);
+        let actual = color_at(Arc::new(w), r, DEFAULT_MAX_DEPTH);
         let expected = Color::new(0.38066, 0.47583, 0.2855);
 
         assert_eq!(actual, expected);
@@ -146,9 +149,48 @@ mod world_tests {
 
         let r = Ray::new(Tuple::point(0.0, 0.0, 0.75), Tuple::vector(0.0, 0.0, -1.0));
 
-        let actual = color_at(w.clone(), r);
+        let actual = color_at(w.clone(), r, DEFAULT_MAX_DEPTH);
         let expected = inner.clone().lock().unwrap().material().color;
 
         assert_eq!(actual, expected);
     }
+
+    #[test]
+    fn test_shading_sums_all_lights() {
+        let mut w = World::default_world();
+        w.light_sources.push(Box::new(PointLight::new(
+            Tuple::point(-10.0, 10.0, -10.0),
+            Color::new(1.0, 1.0, 1.0),
+        )));
+        let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let i = Arc::new(Intersection::new(4.0, w.objects[0].clone()));
+
+        let comps = prepare_computations(i.clone(), ray, &Intersections::from(vec![i]));
+        let color = shade_hit(Arc::new(w), &comps, DEFAULT_MAX_DEPTH);
+
+        assert_eq!(color, Color::new(0.76132, 0.95166, 0.571));
+    }
+
+    #[test]
+    fn test_shading_shadows_each_light_separately() {
+        let mut w = World::default_world();
+        // Second light straight in front of the hit, but blocked so it only adds ambient
+        w.light_sources.push(Box::new(PointLight::new(
+            Tuple::point(0.0, 0.0, -10.0),
+            Color::new(1.0, 1.0, 1.0),
+        )));
+        let blocker = sphere();
+        blocker
+            .lock()
+            .unwrap()
+            .set_transformation(&scale(0.5, 0.5, 0.5).translate(0.0, 0.0, -5.0));
+        w.objects.push(blocker);
+        let ray = Ray::new(Tuple::point(0.0, 0.0, -5.0), Tuple::vector(0.0, 0.0, 1.0));
+        let i = Arc::new(Intersection::new(4.0, w.objects[0].clone()));
+
+        let comps = prepare_computations(i.clone(), ray, &Intersections::from(vec![i]));
+        let color = shade_hit(Arc::new(w), &comps, DEFAULT_MAX_DEPTH);
+
+        assert_eq!(color, Color::new(0.46066, 0.57583, 0.3455));
+    }
 }